@@ -0,0 +1,117 @@
+//! Parse, part1 and part2 time of every day, on its example and real inputs.
+//!
+//! `cargo bench -p aoc -- day-16` runs a single day.
+//! Cases taking many seconds per run are skipped unless AOC_BENCH_SLOW is set.
+use std::{
+    env, fs,
+    hint::black_box,
+    path::{Path, PathBuf},
+};
+
+use common::{Settings, Solver};
+use criterion::{criterion_group, criterion_main, Criterion, SamplingMode};
+
+// (day, input, part) taking more than a few seconds per run
+const SLOW: [(u32, &str, u8); 5] = [
+    (16, "input", 1),
+    (16, "input", 2),
+    (19, "example", 2),
+    (19, "input", 1),
+    (19, "input", 2),
+];
+// Days with runs of 100ms or more, measured with fewer samples
+const LONG: [u32; 9] = [11, 12, 14, 15, 16, 19, 20, 23, 24];
+
+fn day_dir(day: u32) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("../day-{:02}", day))
+}
+
+/// Example and input files of a day, as (name, content), sorted by name.
+fn inputs(day: u32) -> Vec<(String, String)> {
+    let mut inputs = fs::read_dir(day_dir(day))
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|p| {
+                    let name = p.file_stem()?.to_str()?.to_string();
+                    if name.starts_with("example") || name.starts_with("input") {
+                        Some((name, fs::read_to_string(&p).ok()?))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    inputs.sort();
+    inputs
+}
+
+fn bench_day<S: Solver>(c: &mut Criterion, day: u32) {
+    let slow = env::var_os("AOC_BENCH_SLOW").is_some();
+    let run = |name: &str, part: u8| slow || !SLOW.contains(&(day, name, part));
+    let mut group = c.benchmark_group(format!("day-{:02}", day));
+    if LONG.contains(&day) {
+        group.sample_size(10).sampling_mode(SamplingMode::Flat);
+    }
+    for (name, content) in inputs(day) {
+        let Ok(input) = S::parse(&content) else {
+            eprintln!("day-{:02}/{}.txt cannot be parsed, skipped", day, name);
+            continue;
+        };
+        // Parameters of the input config file, like the day-15 example ones
+        let file = day_dir(day).join(format!("{}.txt", name));
+        let params = Settings::config(&file.to_string_lossy()).and_then(|s| s.apply::<S::Params>());
+        let Ok(params) = params else {
+            eprintln!("day-{:02}/{}.toml cannot be read, skipped", day, name);
+            continue;
+        };
+        group.bench_function(format!("parse/{}", name), |b| {
+            b.iter(|| S::parse(black_box(&content)))
+        });
+        if run(&name, 1) {
+            group.bench_function(format!("part1/{}", name), |b| {
+                b.iter(|| S::part1(black_box(&input), &params))
+            });
+        }
+        if run(&name, 2) {
+            group.bench_function(format!("part2/{}", name), |b| {
+                b.iter(|| S::part2(black_box(&input), &params))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day_01::Solution>(c, 1);
+    bench_day::<day_02::Solution>(c, 2);
+    bench_day::<day_03::Solution>(c, 3);
+    bench_day::<day_04::Solution>(c, 4);
+    bench_day::<day_05::Solution>(c, 5);
+    bench_day::<day_06::Solution>(c, 6);
+    bench_day::<day_07::Solution>(c, 7);
+    bench_day::<day_08::Solution>(c, 8);
+    bench_day::<day_09::Solution>(c, 9);
+    bench_day::<day_10::Solution>(c, 10);
+    bench_day::<day_11::Solution>(c, 11);
+    bench_day::<day_12::Solution>(c, 12);
+    bench_day::<day_13::Solution>(c, 13);
+    bench_day::<day_14::Solution>(c, 14);
+    bench_day::<day_15::Solution>(c, 15);
+    bench_day::<day_16::Solution>(c, 16);
+    bench_day::<day_17::Solution>(c, 17);
+    bench_day::<day_18::Solution>(c, 18);
+    bench_day::<day_19::Solution>(c, 19);
+    bench_day::<day_20::Solution>(c, 20);
+    bench_day::<day_21::Solution>(c, 21);
+    bench_day::<day_22::Solution>(c, 22);
+    bench_day::<day_23::Solution>(c, 23);
+    bench_day::<day_24::Solution>(c, 24);
+    bench_day::<day_25::Solution>(c, 25);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
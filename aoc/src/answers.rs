@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+
+/// Known good answers, by input file then part, as read from `answers.toml`:
+/// ```toml
+/// ["day-01/input.txt"]
+/// part1 = "69177"
+/// part2 = 207456
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    files: BTreeMap<String, [Option<String>; 2]>,
+}
+
+/// Comparison of an answer with the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    /// Differs from the recorded answer, given.
+    Fail(String),
+    /// No answer recorded yet.
+    Unrecorded,
+}
+
+impl Answers {
+    pub fn parse(content: &str) -> Result<Answers, String> {
+        let table = content.parse::<toml::Table>().map_err(|e| e.to_string())?;
+        let mut files = BTreeMap::new();
+        for (file, parts) in table {
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("`{}` must be a table of parts", file))?;
+            let mut answers = [None, None];
+            for (key, value) in parts {
+                let part = match key.as_str() {
+                    "part1" => 0,
+                    "part2" => 1,
+                    _ => return Err(format!("`{}` is not a part of `{}`", key, file)),
+                };
+                answers[part] = Some(match value {
+                    toml::Value::String(s) => s.clone(),
+                    toml::Value::Integer(i) => i.to_string(),
+                    _ => return Err(format!("`{}.{}` must be a string or an integer", file, key)),
+                });
+            }
+            files.insert(file, answers);
+        }
+        Ok(Answers { files })
+    }
+    pub fn get(&self, file: &str, part: u8) -> Option<&str> {
+        self.files.get(file)?.get(part as usize - 1)?.as_deref()
+    }
+    /// Recorded input files of a day, the ones in its `day-XX` directory.
+    pub fn files(&self, day: u32) -> impl Iterator<Item = &str> {
+        let dir = format!("day-{:02}/", day);
+        self.files
+            .keys()
+            .filter(move |file| file.starts_with(&dir))
+            .map(|file| file.as_str())
+    }
+    pub fn check(&self, file: &str, part: u8, answer: &str) -> Verdict {
+        match self.get(file, part) {
+            None => Verdict::Unrecorded,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check() {
+        let answers = Answers::parse(concat!(
+            "[\"day-01/input.txt\"]\n",
+            "part1 = \"69177\"\n",
+            "part2 = 207456\n",
+            "[\"day-10/input.txt\"]\n",
+            "part2 = '''\n",
+            "#..#\n",
+            "'''\n",
+        ))
+        .unwrap();
+        assert_eq!(answers.check("day-01/input.txt", 1, "69177"), Verdict::Pass);
+        assert_eq!(
+            answers.check("day-01/input.txt", 2, "207456"),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check("day-01/input.txt", 2, "1"),
+            Verdict::Fail("207456".to_string())
+        );
+        assert_eq!(
+            answers.check("day-10/input.txt", 2, "#..#\n"),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check("day-10/input.txt", 1, "1"),
+            Verdict::Unrecorded
+        );
+        assert_eq!(
+            answers.files(10).collect::<Vec<_>>(),
+            vec!["day-10/input.txt"]
+        );
+        assert!(Answers::parse("[\"day-01/input.txt\"]\npart3 = 1\n").is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use common::{Image, Simulation};
+
+/// Where and how the images of `aoc image` are written.
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub dir: PathBuf,
+    /// Start of the file names, like `day-14-part-1`
+    pub prefix: String,
+    /// Ticks between two frames of a simulation
+    pub every: usize,
+    /// Frames of a simulation at most
+    pub frames: usize,
+    /// Pixels per cell
+    pub scale: usize,
+}
+
+impl Options {
+    /// Writes an image as `<dir>/<prefix>-<name>.<extension>`.
+    pub fn write(&self, name: &str, image: &Image) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let file = format!("{}-{}.{}", self.prefix, name, image.extension());
+        let path = Path::new(&self.dir).join(file);
+        let image = match self.scale {
+            1 => image.to_netpbm(),
+            scale => image.scale(scale).to_netpbm(),
+        };
+        fs::write(&path, image)?;
+        Ok(path)
+    }
+}
+
+/// Writes the frames of a simulation, named by their tick: its start, every
+/// `every` ticks, and its end, `frames` at most. None if the simulation has no
+/// image.
+pub fn frames(
+    mut simulation: Box<dyn Simulation>,
+    options: &Options,
+) -> io::Result<Option<Vec<PathBuf>>> {
+    let Some(image) = simulation.image() else {
+        return Ok(None);
+    };
+    let mut written = vec![options.write("00000", &image)?];
+    let mut tick = 0;
+    let mut last_written = true;
+    while written.len() < options.frames {
+        if !simulation.step() {
+            break;
+        }
+        tick += 1;
+        last_written = tick % options.every == 0;
+        if last_written {
+            written.push(write_frame(simulation.as_ref(), tick, options)?);
+        }
+    }
+    if !last_written && written.len() < options.frames {
+        written.push(write_frame(simulation.as_ref(), tick, options)?);
+    }
+    Ok(Some(written))
+}
+
+fn write_frame(simulation: &dyn Simulation, tick: usize, options: &Options) -> io::Result<PathBuf> {
+    let image = simulation.image().expect("image of every frame");
+    options.write(&format!("{:05}", tick), &image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Settings;
+    use std::env;
+
+    #[test]
+    fn sand_frames() {
+        let content = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+        let replay = crate::replay(14, 1, content, &Settings::default())
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let options = Options {
+            dir: env::temp_dir().join(format!("aoc-export-{}", std::process::id())),
+            prefix: String::from("day-14-part-1"),
+            every: 10,
+            frames: 100,
+            scale: 2,
+        };
+        let written = frames(replay(), &options).unwrap().unwrap();
+        let names = written
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        let expected =
+            ["00000", "00010", "00020", "00024"].map(|tick| format!("day-14-part-1-{}.ppm", tick));
+        assert_eq!(names, expected);
+        let last = fs::read_to_string(&written[3]).unwrap();
+        assert!(last.starts_with("P3\n20 16\n255\n"));
+
+        let few = Options {
+            frames: 2,
+            ..options.clone()
+        };
+        assert_eq!(frames(replay(), &few).unwrap().unwrap().len(), 2);
+        fs::remove_dir_all(&options.dir).unwrap();
+
+        let rope = crate::replay(9, 1, "R 4\n", &Settings::default());
+        assert!(frames(rope.unwrap().unwrap().unwrap()(), &options)
+            .unwrap()
+            .is_none());
+    }
+}
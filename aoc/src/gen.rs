@@ -0,0 +1,1190 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+};
+
+use common::Settings;
+
+/// Small seeded random generator ( SplitMix64 ), the same seed gives the same
+/// input on every platform.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    /// Uniform in the inclusive range
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (low, high) = range.into_inner();
+        low + (self.next_u64() % ((high - low) as u64 + 1)) as i64
+    }
+    /// Uniform in 0..n
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+    pub fn chance(&mut self, percent: u32) -> bool {
+        self.next_u64() % 100 < percent as u64
+    }
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+    fn letter(&mut self, letters: &str) -> char {
+        *self.choose(letters.as_bytes()) as char
+    }
+}
+
+/// Default size of the generated inputs, close to the puzzle inputs, their
+/// smallest size and what the size counts.
+pub const SIZES: [(usize, usize, &str); 25] = [
+    (250, 1, "elves"),
+    (2500, 1, "rounds"),
+    (300, 1, "rucksacks, by groups of 3"),
+    (1000, 1, "pairs"),
+    (500, 1, "moves"),
+    (4096, 28, "characters"),
+    (300, 1, "files"),
+    (99, 1, "trees on a side"),
+    (2000, 1, "motions"),
+    (6, 6, "screen rows"),
+    (8, 2, "monkeys, 9 at most"),
+    (41, 10, "rows"),
+    (150, 1, "pairs"),
+    (140, 1, "rock paths"),
+    (27, 4, "sensors, more when needed to hide the beacon"),
+    (59, 2, "valves"),
+    (10091, 1, "jets"),
+    (2164, 1, "cubes"),
+    (30, 1, "blueprints"),
+    (5000, 1, "numbers"),
+    (1901, 3, "monkeys"),
+    (2000, 1, "moves of the path"),
+    (70, 1, "rows and columns"),
+    (100, 2, "columns"),
+    (119, 1, "numbers"),
+];
+
+/// Random input of a day, of `size` elements ( see `SIZES` ), shaped by the
+/// generator parameters of the day in `settings`.
+pub fn generate(
+    day: u32,
+    size: Option<usize>,
+    seed: u64,
+    settings: &Settings,
+) -> Result<String, String> {
+    let sizes = (day as usize).checked_sub(1).and_then(|i| SIZES.get(i));
+    let Some(&(default_size, min_size, what)) = sizes else {
+        return Err(format!("no day {}", day));
+    };
+    let size = size.unwrap_or(default_size);
+    if size < min_size {
+        return Err(format!("day {} needs at least {} {}", day, min_size, what));
+    }
+    let rng = &mut Rng::new(seed);
+    match day {
+        1 => Ok(day_01(rng, size, &settings.apply()?)),
+        2 => Ok(day_02(rng, size, &settings.apply()?)),
+        3 => Ok(day_03(rng, size, &settings.apply()?)),
+        4 => Ok(day_04(rng, size, &settings.apply()?)),
+        5 => Ok(day_05(rng, size, &settings.apply()?)),
+        6 => Ok(day_06(rng, size, &settings.apply()?)),
+        7 => Ok(day_07(rng, size, &settings.apply()?)),
+        8 => Ok(day_08(rng, size, &settings.apply()?)),
+        9 => Ok(day_09(rng, size, &settings.apply()?)),
+        10 => Ok(day_10(rng, size, &settings.apply()?)),
+        11 => day_11(rng, size, &settings.apply()?),
+        12 => Ok(day_12(rng, size, &settings.apply()?)),
+        13 => Ok(day_13(rng, size, &settings.apply()?)),
+        14 => Ok(day_14(rng, size, &settings.apply()?)),
+        15 => day_15(rng, size, &settings.apply()?),
+        16 => day_16(rng, size, &settings.apply()?),
+        17 => Ok(day_17(rng, size, &settings.apply()?)),
+        18 => day_18(rng, size, &settings.apply()?),
+        19 => day_19(rng, size, &settings.apply()?),
+        20 => Ok(day_20(rng, size, &settings.apply()?)),
+        21 => Ok(day_21(rng, size, &settings.apply()?)),
+        22 => day_22(rng, size, &settings.apply()?),
+        23 => Ok(day_23(rng, size, &settings.apply()?)),
+        24 => day_24(rng, size, &settings.apply()?),
+        _ => day_25(rng, size, &settings.apply()?),
+    }
+}
+
+const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn word(rng: &mut Rng, len: RangeInclusive<i64>) -> String {
+    let len = rng.range(len);
+    (0..len).map(|_| rng.letter(LOWER)).collect()
+}
+
+fn day_01(rng: &mut Rng, size: usize, _: &()) -> String {
+    let elves = (0..size)
+        .map(|_| {
+            (0..rng.range(1..=14))
+                .map(|_| format!("{}\n", rng.range(1000..=60000)))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    elves.join("\n")
+}
+
+fn day_02(rng: &mut Rng, size: usize, _: &()) -> String {
+    (0..size)
+        .map(|_| format!("{} {}\n", rng.letter("ABC"), rng.letter("XYZ")))
+        .collect()
+}
+
+// The 52 items of a group share out between the badge and 17 items for each
+// rucksack, so that a rucksack has a single item in both compartments and the
+// group a single item in all three rucksacks
+fn day_03(rng: &mut Rng, size: usize, _: &()) -> String {
+    let mut content = String::new();
+    for _ in 0..size.div_ceil(3) {
+        let mut items = format!("{}{}", LOWER, UPPER).chars().collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        let badge = items[0];
+        for own in items[1..].chunks(17) {
+            let shared = own[0];
+            let len = rng.range(8..=16) as usize;
+            let mut first = vec![badge, shared];
+            first.extend((2..len).map(|_| *rng.choose(&own[1..9])));
+            let mut second = vec![shared];
+            second.extend((1..len).map(|_| *rng.choose(&own[9..])));
+            rng.shuffle(&mut first);
+            rng.shuffle(&mut second);
+            content.extend(first.iter().chain(second.iter()));
+            content.push('\n');
+        }
+    }
+    content
+}
+
+fn day_04(rng: &mut Rng, size: usize, _: &()) -> String {
+    let mut content = String::new();
+    for _ in 0..size {
+        let (a, c) = (rng.range(1..=99), rng.range(1..=99));
+        let (b, d) = (rng.range(a..=99), rng.range(c..=99));
+        content += &format!("{}-{},{}-{}\n", a, b, c, d);
+    }
+    content
+}
+
+// 9 stacks, a move never empties a stack
+fn day_05(rng: &mut Rng, size: usize, _: &()) -> String {
+    let mut heights = (0..9)
+        .map(|_| rng.range(1..=8) as usize)
+        .collect::<Vec<_>>();
+    heights[rng.below(9)] = 8;
+    let mut content = String::new();
+    for row in (0..8).rev() {
+        let line = heights
+            .iter()
+            .map(|&h| match h > row {
+                true => format!("[{}]", rng.letter(UPPER)),
+                false => String::from("   "),
+            })
+            .collect::<Vec<_>>();
+        content += &line.join(" ");
+        content.push('\n');
+    }
+    let numbers = (1..=9).map(|i| format!(" {} ", i)).collect::<Vec<_>>();
+    content += &format!("{}\n\n", numbers.join(" "));
+    for _ in 0..size {
+        let from = loop {
+            let from = rng.below(9);
+            if heights[from] >= 2 {
+                break from;
+            }
+        };
+        let to = (from + 1 + rng.below(8)) % 9;
+        let qty = rng.range(1..=heights[from] as i64 - 1) as usize;
+        heights[from] -= qty;
+        heights[to] += qty;
+        content += &format!("move {} from {} to {}\n", qty, from + 1, to + 1);
+    }
+    content
+}
+
+// Few letters up to the middle, so that the start-of-message marker is the
+// 14 different letters put there
+fn day_06(rng: &mut Rng, size: usize, _: &()) -> String {
+    let mut letters = LOWER.chars().collect::<Vec<_>>();
+    rng.shuffle(&mut letters);
+    let mut content = (0..size / 2)
+        .map(|_| *rng.choose(&letters[..8]))
+        .collect::<String>();
+    rng.shuffle(&mut letters);
+    content.extend(&letters[..14]);
+    content.extend((size / 2 + 14..size).map(|_| rng.letter(LOWER)));
+    content.push('\n');
+    content
+}
+
+// A used space between 41 and 69 MB of the 70 MB disk, so that part 2 has to
+// free some
+fn day_07(rng: &mut Rng, size: usize, _: &()) -> String {
+    let n_dir = size / 5 + 1;
+    // Entries of every directory by name, with the index of the subdirectories
+    let mut entries = vec![BTreeMap::new(); n_dir];
+    for dir in 1..n_dir {
+        let parent = rng.below(dir);
+        let name = unique_name(rng, &entries[parent], false);
+        entries[parent].insert(name, Some(dir));
+    }
+    for _ in 0..size {
+        let dir = rng.below(n_dir);
+        let name = unique_name(rng, &entries[dir], true);
+        entries[dir].insert(name, None);
+    }
+    // Mostly small files, for the directories of at most 100000 of part 1
+    let mut sizes = (0..size)
+        .map(|_| {
+            if rng.chance(70) {
+                rng.range(1_000..=40_000) as u64
+            } else {
+                rng.range(100_000..=350_000) as u64
+            }
+        })
+        .collect::<Vec<_>>();
+    let total = sizes.iter().sum::<u64>();
+    if !(41_000_000..=69_000_000).contains(&total) {
+        let target = rng.range(41_000_000..=69_000_000) as u64;
+        // Only the large files make up the difference, unless there are none
+        // or the small ones are already too many
+        let small = sizes.iter().filter(|&&s| s < 100_000).sum::<u64>();
+        let keep = if small < total && small < target {
+            small
+        } else {
+            0
+        };
+        for s in sizes.iter_mut().filter(|s| keep == 0 || **s >= 100_000) {
+            *s = (*s * (target - keep) / (total - keep)).max(1);
+        }
+    }
+    let mut content = String::from("$ cd /\n");
+    list(0, &entries, &mut sizes.into_iter(), &mut content);
+    content
+}
+
+fn unique_name(rng: &mut Rng, entries: &BTreeMap<String, Option<usize>>, file: bool) -> String {
+    loop {
+        let mut name = word(rng, 1..=8);
+        if file && rng.chance(50) {
+            name = format!("{}.{}", name, word(rng, 3..=3));
+        }
+        if !entries.contains_key(&name) {
+            return name;
+        }
+    }
+}
+
+fn list(
+    dir: usize,
+    entries: &[BTreeMap<String, Option<usize>>],
+    sizes: &mut impl Iterator<Item = u64>,
+    content: &mut String,
+) {
+    *content += "$ ls\n";
+    for (name, entry) in entries[dir].iter() {
+        match entry {
+            Some(_) => *content += &format!("dir {}\n", name),
+            None => *content += &format!("{} {}\n", sizes.next().unwrap(), name),
+        }
+    }
+    for (name, entry) in entries[dir].iter() {
+        if let Some(sub) = entry {
+            *content += &format!("$ cd {}\n", name);
+            list(*sub, entries, sizes, content);
+            *content += "$ cd ..\n";
+        }
+    }
+}
+
+fn day_08(rng: &mut Rng, size: usize, _: &()) -> String {
+    (0..size)
+        .map(|_| {
+            let mut row = (0..size)
+                .map(|_| rng.letter("0123456789"))
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+fn day_09(rng: &mut Rng, size: usize, _: &()) -> String {
+    (0..size)
+        .map(|_| format!("{} {}\n", rng.letter("UDLR"), rng.range(1..=19)))
+        .collect()
+}
+
+// 40 cycles by screen row, X stays around the screen
+fn day_10(rng: &mut Rng, size: usize, _: &()) -> String {
+    let (mut x, mut cycle) = (1, 0);
+    let mut content = String::new();
+    while cycle < 40 * size {
+        if cycle + 1 == 40 * size || rng.chance(25) {
+            content += "noop\n";
+            cycle += 1;
+            continue;
+        }
+        let mut v = rng.range(1..=15);
+        if (x + v > 40 || rng.chance(50)) && x - v >= -1 {
+            v = -v;
+        }
+        x += v;
+        content += &format!("addx {}\n", v);
+        cycle += 2;
+    }
+    content
+}
+
+// Different prime divisors, whose product squared fits the worry levels. A
+// blank line ends every monkey, as in the puzzle inputs
+fn day_11(rng: &mut Rng, size: usize, _: &()) -> Result<String, String> {
+    if size > 9 {
+        return Err(String::from(
+            "day 11 has 9 monkeys at most, for the worry levels to fit in 64 bits",
+        ));
+    }
+    let mut primes = vec![2, 3, 5, 7, 11, 13, 17, 19, 23];
+    rng.shuffle(&mut primes);
+    let square = rng.below(size);
+    let monkeys = (0..size)
+        .map(|i| {
+            let items = (0..rng.range(1..=8))
+                .map(|_| rng.range(50..=99).to_string())
+                .collect::<Vec<_>>();
+            let operation = match (i == square, rng.chance(50)) {
+                (true, _) => String::from("old * old"),
+                (false, true) => format!("old * {}", rng.range(2..=19)),
+                (false, false) => format!("old + {}", rng.range(1..=8)),
+            };
+            let if_true = (i + 1 + rng.below(size - 1)) % size;
+            let if_false = loop {
+                let dest = (i + 1 + rng.below(size - 1)) % size;
+                if dest != if_true || size == 2 {
+                    break dest;
+                }
+            };
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n\n",
+                i,
+                items.join(", "),
+                operation,
+                primes[i],
+                if_true,
+                if_false
+            )
+        })
+        .collect();
+    Ok(monkeys)
+}
+
+// A climb along a loop-erased random walk from S to E, on slopes going down
+// from E
+fn day_12(rng: &mut Rng, size: usize, _: &()) -> String {
+    let (width, height) = (4 * size as i64, size as i64);
+    let start = (0, height / 2);
+    let end = (width * 3 / 4, height / 2);
+    let mut heights = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    let dist = (x - end.0).abs() + (y - end.1).abs();
+                    (25 - dist / 3 - rng.range(0..=1)).max(0)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut path = vec![start];
+    let mut on_path = BTreeMap::from([(start, 0)]);
+    let mut pos = start;
+    while pos != end {
+        let (dx, dy) = if rng.chance(60) {
+            match rng.chance(50) {
+                true if pos.0 != end.0 => ((end.0 - pos.0).signum(), 0),
+                _ if pos.1 != end.1 => (0, (end.1 - pos.1).signum()),
+                _ => ((end.0 - pos.0).signum(), 0),
+            }
+        } else {
+            *rng.choose(&[(1, 0), (-1, 0), (0, 1), (0, -1)])
+        };
+        let next = (pos.0 + dx, pos.1 + dy);
+        if next.0 < 0 || next.0 >= width || next.1 < 0 || next.1 >= height {
+            continue;
+        }
+        if let Some(&i) = on_path.get(&next) {
+            for erased in path.drain(i + 1..) {
+                on_path.remove(&erased);
+            }
+        } else {
+            on_path.insert(next, path.len());
+            path.push(next);
+        }
+        pos = next;
+    }
+    for (i, &(x, y)) in path.iter().enumerate() {
+        heights[y as usize][x as usize] = 25 * i as i64 / (path.len() as i64 - 1);
+    }
+    let mut content = String::new();
+    for (y, row) in heights.iter().enumerate() {
+        for (x, &h) in row.iter().enumerate() {
+            content.push(match (x as i64, y as i64) {
+                p if p == start => 'S',
+                p if p == end => 'E',
+                _ => (b'a' + h as u8) as char,
+            });
+        }
+        content.push('\n');
+    }
+    content
+}
+
+fn packet(rng: &mut Rng, depth: u32) -> String {
+    let items = (0..rng.range(0..=4))
+        .map(|_| match depth < 4 && rng.chance(30) {
+            true => packet(rng, depth + 1),
+            false => rng.range(0..=10).to_string(),
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(","))
+}
+
+fn day_13(rng: &mut Rng, size: usize, _: &()) -> String {
+    let pairs = (0..size)
+        .map(|_| format!("{}\n{}\n", packet(rng, 0), packet(rng, 0)))
+        .collect::<Vec<_>>();
+    pairs.join("\n")
+}
+
+// Rock paths on both sides of the column under the sand source, which only a
+// ledge below every other rock crosses: the sand piles up on the ledge, then
+// falls past its ends into the abyss, before part 2 fills the cave
+fn day_14(rng: &mut Rng, size: usize, _: &()) -> String {
+    let half = rng.range(3..=10);
+    let (left, right) = (500 - half - 2, 500 + half + 2);
+    let mut paths = vec![];
+    let mut deepest = 0;
+    for _ in 1..size {
+        let (low, high) = if rng.chance(50) {
+            (0, left)
+        } else {
+            (right, 1000)
+        };
+        let (mut x, mut y) = (rng.range(460..=540).clamp(low, high), rng.range(13..=170));
+        let mut points = vec![format!("{},{}", x, y)];
+        deepest = deepest.max(y);
+        let horizontal = rng.chance(50);
+        for i in 0..rng.range(1..=5) {
+            let step = rng.range(1..=10) * if rng.chance(50) { 1 } else { -1 };
+            if (i % 2 == 0) == horizontal {
+                x = (x + step).clamp(low, high);
+            } else {
+                y = (y + step).clamp(1, 1000);
+            }
+            deepest = deepest.max(y);
+            points.push(format!("{},{}", x, y));
+        }
+        paths.push(points.join(" -> "));
+    }
+    let y = deepest.max(13) + rng.range(1..=5);
+    let ledge = format!("{},{} -> {},{}", 500 - half, y, 500 + half, y);
+    paths.insert(rng.below(paths.len() + 1), ledge);
+    paths.iter().map(|path| format!("{}\n", path)).collect()
+}
+
+common::params! {
+    /// Day-15 generator parameters
+    pub struct Sensors {
+        /// Search area of the distress beacon, from 0 to max in x and y
+        pub max: i32 = 4_000_000,
+    }
+}
+
+// Sensor position, distance and closest beacon
+type Sensor = ((i64, i64), i64, (i64, i64));
+
+fn dist(a: (i64, i64), b: (i64, i64)) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+// Sensors covering every position of the search area but the distress beacon,
+// the way day-15 counts them: without the top and bottom tips of a sensor
+// range. Four sensors around the distress beacon cover its neighbours, the
+// others are random, then a sensor is added on the first position left
+// until none is. A new beacon is never within the range of another sensor.
+fn day_15(rng: &mut Rng, size: usize, params: &Sensors) -> Result<String, String> {
+    let max = params.max as i64;
+    if max < 10 {
+        return Err(String::from("the search area must be at least 10 wide"));
+    }
+    let (hidden, mut sensors) = loop {
+        let hidden = (
+            rng.range(max / 10..=max * 9 / 10),
+            rng.range(max / 10..=max * 9 / 10),
+        );
+        let mut sensors: Vec<Sensor> = vec![];
+        for (sx, sy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+            let a = rng.range(2.max(max / 40)..=2.max(max / 8));
+            let pos = (hidden.0 + sx * a, hidden.1 + sy * a);
+            sensors.push((pos, 2 * a - 1, pos));
+        }
+        // A wide sensor can leave too little of a narrow one's edge out of
+        // its range: draw the four of them again
+        let placed = (0..4).all(|i| {
+            let (pos, radius, _) = sensors[i];
+            beacon(rng, pos, radius, &sensors, i)
+                .map(|b| sensors[i].2 = b)
+                .is_some()
+        });
+        if placed {
+            break (hidden, sensors);
+        }
+    };
+    for _ in 4..size {
+        let pos = (rng.range(0..=max), rng.range(0..=max));
+        let radius = dist(pos, hidden) - 1;
+        let radius = radius - rng.range(0..=radius / 3);
+        if let Some(sensor) = add_sensor(rng, pos, radius, &sensors) {
+            sensors.push(sensor);
+        }
+    }
+    let mut row = 0;
+    while let Some(pos) = uncovered(&sensors, hidden, max, row) {
+        row = pos.1;
+        let sensor = add_sensor(rng, pos, dist(pos, hidden) - 1, &sensors)
+            .ok_or("no room for a sensor, try another seed")?;
+        sensors.push(sensor);
+    }
+    rng.shuffle(&mut sensors);
+    Ok(sensors
+        .iter()
+        .map(|((x, y), _, (bx, by))| {
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                x, y, bx, by
+            )
+        })
+        .collect())
+}
+
+// A sensor at `pos` with at most the given radius, that keeps the other
+// beacons out of its range and the new beacon out of the other ranges
+fn add_sensor(rng: &mut Rng, pos: (i64, i64), radius: i64, sensors: &[Sensor]) -> Option<Sensor> {
+    let beacons = sensors.iter().map(|s| s.2).collect::<BTreeSet<_>>();
+    let closest = beacons
+        .iter()
+        .map(|&b| dist(pos, b))
+        .min()
+        .unwrap_or(i64::MAX);
+    if closest == 0 || sensors.iter().any(|&(s, _, _)| s == pos) {
+        return None;
+    }
+    let shared = beacons.iter().filter(|&&b| dist(pos, b) == closest).count();
+    if closest <= radius && shared == 1 {
+        let b = *beacons.iter().find(|&&b| dist(pos, b) == closest).unwrap();
+        return Some((pos, closest, b));
+    }
+    let mut radius = radius.min(closest - 1);
+    while radius >= 1 {
+        if let Some(b) = beacon(rng, pos, radius, sensors, sensors.len()) {
+            return Some((pos, radius, b));
+        }
+        radius = radius * 3 / 4;
+    }
+    None
+}
+
+// A random position at `radius` of `pos`, out of the ranges of the sensors
+// other than `own`
+fn beacon(
+    rng: &mut Rng,
+    pos: (i64, i64),
+    radius: i64,
+    sensors: &[Sensor],
+    own: usize,
+) -> Option<(i64, i64)> {
+    (0..50).find_map(|_| {
+        let dx = rng.range(-radius..=radius);
+        let dy = (radius - dx.abs()) * if rng.chance(50) { 1 } else { -1 };
+        let b = (pos.0 + dx, pos.1 + dy);
+        let free = sensors
+            .iter()
+            .enumerate()
+            .all(|(i, &(s, r, _))| i == own || dist(b, s) > r);
+        free.then_some(b)
+    })
+}
+
+// First position of the search area from `row`, other than the distress
+// beacon, out of the ranges of the sensors
+fn uncovered(sensors: &[Sensor], hidden: (i64, i64), max: i64, row: i64) -> Option<(i64, i64)> {
+    for y in row..=max {
+        let mut ranges = sensors
+            .iter()
+            .filter(|&&((_, sy), r, _)| (sy - y).abs() < r)
+            .map(|&((sx, sy), r, _)| (sx - (r - (sy - y).abs()), sx + (r - (sy - y).abs())))
+            .collect::<Vec<_>>();
+        ranges.sort_unstable();
+        let mut x = 0;
+        for (low, high) in ranges.into_iter().chain([(max + 2, max + 2)]) {
+            if low > x && x <= max {
+                if (x, y) != hidden {
+                    return Some((x, y));
+                }
+                if x + 1 < low && x < max {
+                    return Some((x + 1, y));
+                }
+            }
+            x = x.max(high + 1);
+        }
+    }
+    None
+}
+
+common::params! {
+    /// Day-16 generator parameters
+    pub struct Valves {
+        /// Valves with a flow rate
+        pub valves: usize = 15,
+    }
+}
+
+// A random tree of tunnels with a few more tunnels, the valves with a flow
+// rate among the valves other than AA
+fn day_16(rng: &mut Rng, size: usize, params: &Valves) -> Result<String, String> {
+    if size > 26 * 26 {
+        return Err(format!("day 16 has {} valves at most", 26 * 26));
+    }
+    if params.valves >= size {
+        return Err(String::from("AA and other valves have no flow rate"));
+    }
+    let mut names = UPPER
+        .chars()
+        .flat_map(|a| UPPER.chars().map(move |b| format!("{}{}", a, b)))
+        .skip(1)
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut names);
+    names.truncate(size - 1);
+    names.insert(rng.below(size), String::from("AA"));
+    let mut pairs = (1..size).map(|v| (v, rng.below(v))).collect::<Vec<_>>();
+    pairs.extend((0..size / 5).map(|_| (rng.below(size), rng.below(size))));
+    let mut tunnels = vec![BTreeSet::new(); size];
+    for (a, b) in pairs.into_iter().filter(|(a, b)| a != b) {
+        tunnels[a].insert(b);
+        tunnels[b].insert(a);
+    }
+    let mut rates = vec![0; size];
+    let mut with_rate = (0..size).filter(|&v| names[v] != "AA").collect::<Vec<_>>();
+    rng.shuffle(&mut with_rate);
+    for &valve in with_rate.iter().take(params.valves) {
+        rates[valve] = rng.range(3..=25);
+    }
+    let mut order = (0..size).collect::<Vec<_>>();
+    rng.shuffle(&mut order);
+    Ok(order
+        .into_iter()
+        .map(|v| {
+            let mut next = tunnels[v]
+                .iter()
+                .map(|&t| names[t].as_str())
+                .collect::<Vec<_>>();
+            rng.shuffle(&mut next);
+            let lead = match next.len() {
+                1 => "tunnel leads to valve",
+                _ => "tunnels lead to valves",
+            };
+            format!(
+                "Valve {} has flow rate={}; {} {}\n",
+                names[v],
+                rates[v],
+                lead,
+                next.join(", ")
+            )
+        })
+        .collect())
+}
+
+fn day_17(rng: &mut Rng, size: usize, _: &()) -> String {
+    let mut content = (0..size).map(|_| rng.letter("<>")).collect::<String>();
+    content.push('\n');
+    content
+}
+
+// Cubes in a ball twice as large as them, with pockets of air inside
+fn day_18(rng: &mut Rng, size: usize, _: &()) -> Result<String, String> {
+    let ball = |side: i64| {
+        let center = (side - 1) as f64 / 2.0;
+        move |x: i64, y: i64, z: i64| {
+            let d = |c: i64| (c as f64 - center).powi(2);
+            d(x) + d(y) + d(z) <= (side as f64 / 2.0).powi(2)
+        }
+    };
+    let volume = |side: i64| {
+        let inside = ball(side);
+        (0..side)
+            .flat_map(|x| (0..side).flat_map(move |y| (0..side).map(move |z| (x, y, z))))
+            .filter(|&(x, y, z)| inside(x, y, z))
+            .count()
+    };
+    let side = (2..=51)
+        .find(|&side| volume(side) >= 2 * size)
+        .unwrap_or(51);
+    if volume(side) < size {
+        return Err(format!("day 18 has {} cubes at most", volume(side)));
+    }
+    let inside = ball(side);
+    let mut cubes = BTreeSet::new();
+    let mut content = String::new();
+    while cubes.len() < size {
+        let cube = (
+            rng.range(0..=side - 1),
+            rng.range(0..=side - 1),
+            rng.range(0..=side - 1),
+        );
+        if inside(cube.0, cube.1, cube.2) && cubes.insert(cube) {
+            content += &format!("{},{},{}\n", cube.0, cube.1, cube.2);
+        }
+    }
+    Ok(content)
+}
+
+common::params! {
+    /// Day-19 generator parameters, the cost ranges of the robots
+    pub struct Blueprints {
+        /// Lowest ore cost
+        pub ore_min: i64 = 2,
+        /// Highest ore cost
+        pub ore_max: i64 = 4,
+        /// Lowest clay cost of an obsidian robot
+        pub clay_min: i64 = 5,
+        /// Highest clay cost of an obsidian robot
+        pub clay_max: i64 = 20,
+        /// Lowest obsidian cost of a geode robot
+        pub obsidian_min: i64 = 5,
+        /// Highest obsidian cost of a geode robot
+        pub obsidian_max: i64 = 20,
+    }
+}
+
+fn day_19(rng: &mut Rng, size: usize, params: &Blueprints) -> Result<String, String> {
+    let ranges = [
+        ("ore", params.ore_min, params.ore_max),
+        ("clay", params.clay_min, params.clay_max),
+        ("obsidian", params.obsidian_min, params.obsidian_max),
+    ];
+    for (name, min, max) in ranges {
+        if min < 1 || min > max {
+            return Err(format!(
+                "the {} costs must be from 1 and up to the highest",
+                name
+            ));
+        }
+    }
+    let ore = params.ore_min..=params.ore_max;
+    let mut content = String::new();
+    for i in 1..=size {
+        content += &format!(
+            "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.\n",
+            i,
+            rng.range(ore.clone()),
+            rng.range(ore.clone()),
+            rng.range(ore.clone()),
+            rng.range(params.clay_min..=params.clay_max),
+            rng.range(ore.clone()),
+            rng.range(params.obsidian_min..=params.obsidian_max),
+        );
+    }
+    Ok(content)
+}
+
+// A single 0 among the numbers
+fn day_20(rng: &mut Rng, size: usize, _: &()) -> String {
+    let mut numbers = (1..size)
+        .map(|_| match rng.range(-10000..=9999) {
+            n if n >= 0 => n + 1,
+            n => n,
+        })
+        .collect::<Vec<_>>();
+    numbers.insert(rng.below(size), 0);
+    numbers.iter().map(|n| format!("{}\n", n)).collect()
+}
+
+// Monkey job, a number or an operation on two monkeys
+enum Job {
+    Number(u64),
+    Operation(usize, char, usize),
+}
+
+// Values stay well within 64 bits
+const JOB_LIMIT: u64 = 1_000_000_000_000_000;
+
+fn apply(a: u64, op: char, b: u64) -> Option<u64> {
+    let value = match op {
+        '+' => a.checked_add(b)?,
+        '-' => a.checked_sub(b).filter(|&v| v > 0)?,
+        '*' => a.checked_mul(b)?,
+        _ => (b > 0 && a.is_multiple_of(b)).then(|| a / b)?,
+    };
+    (value <= JOB_LIMIT).then_some(value)
+}
+
+// A random tree of `leaves` numbers, built from the leaves, and its value.
+// The operations are exact and positive, so that humn can be solved back.
+// The index of the humn number is set in `humn`.
+fn job_tree(
+    rng: &mut Rng,
+    leaves: usize,
+    humn: &mut Option<(usize, u64)>,
+    jobs: &mut Vec<Job>,
+) -> (usize, u64) {
+    if leaves == 1 {
+        let value = match humn {
+            Some((job, value)) if *job == usize::MAX => {
+                *job = jobs.len();
+                *value
+            }
+            _ => rng.range(1..=20) as u64,
+        };
+        jobs.push(Job::Number(value));
+        return (jobs.len() - 1, value);
+    }
+    let left = rng.range(1..=leaves as i64 - 1) as usize;
+    let (mut a, mut va) = job_tree(rng, left, humn, jobs);
+    let (mut b, mut vb) = job_tree(rng, leaves - left, humn, jobs);
+    if va < vb {
+        (a, va, b, vb) = (b, vb, a, va);
+    }
+    // `-` works for different values, `/` for equal ones
+    let ops = ['+', '-', '*', '/']
+        .into_iter()
+        .filter(|&op| apply(va, op, vb).is_some())
+        .collect::<Vec<_>>();
+    let op = *rng.choose(&ops);
+    jobs.push(Job::Operation(a, op, b));
+    (jobs.len() - 1, apply(va, op, vb).unwrap())
+}
+
+fn evaluate(jobs: &[Job], job: usize, humn: (usize, u64)) -> Option<u64> {
+    match jobs[job] {
+        _ if job == humn.0 => Some(humn.1),
+        Job::Number(value) => Some(value),
+        Job::Operation(a, op, b) => apply(evaluate(jobs, a, humn)?, op, evaluate(jobs, b, humn)?),
+    }
+}
+
+// root compares a tree with humn to a tree of the same value, the humn number
+// of the input is another one when it still gives exact operations
+fn day_21(rng: &mut Rng, size: usize, _: &()) -> String {
+    let leaves = size.div_ceil(2).max(3);
+    let answer = rng.range(1..=100_000) as u64;
+    let mut jobs = vec![];
+    let mut humn = Some((usize::MAX, answer));
+    let (left, value) = job_tree(rng, leaves / 2, &mut humn, &mut jobs);
+    let humn = humn.unwrap().0;
+    let (right, other) = job_tree(rng, leaves - leaves / 2 - 1, &mut None, &mut jobs);
+    let (op, fix) = match value >= other {
+        true => ('+', value - other),
+        false => ('-', other - value),
+    };
+    jobs.push(Job::Number(fix));
+    jobs.push(Job::Operation(right, op, jobs.len() - 1));
+    let right = jobs.len() - 1;
+    jobs.push(Job::Operation(left, '+', right));
+    let root = jobs.len() - 1;
+    let shown = (0..10)
+        .map(|_| rng.range(1..=5000) as u64)
+        .find(|&shown| evaluate(&jobs, left, (humn, shown)).is_some())
+        .unwrap_or(answer);
+
+    let mut names = BTreeSet::from([String::from("root"), String::from("humn")]);
+    let mut job_names = vec![String::new(); jobs.len()];
+    for (i, name) in job_names.iter_mut().enumerate() {
+        *name = match i {
+            _ if i == root => String::from("root"),
+            _ if i == humn => String::from("humn"),
+            _ => loop {
+                let name = word(rng, 4..=4);
+                if names.insert(name.clone()) {
+                    break name;
+                }
+            },
+        };
+    }
+    let mut order = (0..jobs.len()).collect::<Vec<_>>();
+    rng.shuffle(&mut order);
+    order
+        .into_iter()
+        .map(|i| match jobs[i] {
+            _ if i == humn => format!("humn: {}\n", shown),
+            Job::Number(value) => format!("{}: {}\n", job_names[i], value),
+            Job::Operation(a, op, b) => format!(
+                "{}: {} {} {}\n",
+                job_names[i], job_names[a], op, job_names[b]
+            ),
+        })
+        .collect()
+}
+
+common::params! {
+    /// Day-22 generator parameters
+    pub struct Cube {
+        /// Width of a cube face
+        pub face: usize = 50,
+        /// Cube net, `random`, a net number from 1 to 11, `input` or `example`
+        pub layout: String = String::from("random"),
+    }
+}
+
+// The 11 nets of a cube, faces by ( column, row ) of faces
+const CUBE_NETS: [[(usize, usize); 6]; 11] = [
+    // A row of 4 faces, one above it and one below
+    [(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (0, 2)],
+    [(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (1, 2)],
+    [(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (2, 2)],
+    [(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (3, 2)],
+    [(1, 0), (0, 1), (1, 1), (2, 1), (3, 1), (1, 2)],
+    [(1, 0), (0, 1), (1, 1), (2, 1), (3, 1), (2, 2)],
+    // Rows of 2, 3 and 1 faces
+    [(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (1, 2)],
+    [(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (2, 2)],
+    [(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (3, 2)],
+    // Stairs of 2 faces
+    [(0, 0), (1, 0), (1, 1), (2, 1), (2, 2), (3, 2)],
+    // 2 rows of 3 faces
+    [(0, 0), (1, 0), (2, 0), (2, 1), (3, 1), (4, 1)],
+];
+
+// A net turned a random number of quarter turns, mirrored or not
+fn orient(rng: &mut Rng, net: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut faces = net
+        .iter()
+        .map(|&(x, y)| (x as i64, y as i64))
+        .collect::<Vec<_>>();
+    for _ in 0..rng.below(4) {
+        faces.iter_mut().for_each(|f| *f = (f.1, -f.0));
+    }
+    if rng.chance(50) {
+        faces.iter_mut().for_each(|f| f.0 = -f.0);
+    }
+    let min_x = faces.iter().map(|f| f.0).min().unwrap();
+    let min_y = faces.iter().map(|f| f.1).min().unwrap();
+    faces
+        .iter()
+        .map(|&(x, y)| ((x - min_x) as usize, (y - min_y) as usize))
+        .collect()
+}
+
+fn day_22(rng: &mut Rng, size: usize, params: &Cube) -> Result<String, String> {
+    let faces = match params.layout.as_str() {
+        "input" => vec![(1, 0), (2, 0), (1, 1), (0, 2), (1, 2), (0, 3)],
+        "example" => vec![(2, 0), (0, 1), (1, 1), (2, 1), (2, 2), (3, 2)],
+        "random" => {
+            let net = *rng.choose(&CUBE_NETS);
+            orient(rng, &net)
+        }
+        layout => match layout.parse::<usize>() {
+            Ok(i) if (1..=CUBE_NETS.len()).contains(&i) => orient(rng, &CUBE_NETS[i - 1]),
+            _ => {
+                return Err(format!(
+                    "unknown layout {}, expected random, 1 to 11, input or example",
+                    params.layout
+                ))
+            }
+        },
+    };
+    if params.face == 0 {
+        return Err(String::from("the faces must be at least 1 wide"));
+    }
+    let n = params.face;
+    let n_row = faces.iter().map(|f| f.1).max().unwrap() + 1;
+    let mut content = String::new();
+    for y in 0..n_row * n {
+        let columns = faces.iter().filter(|f| f.1 == y / n).map(|f| f.0);
+        let (first, last) = (columns.clone().min().unwrap(), columns.max().unwrap());
+        content += &" ".repeat(first * n);
+        for x in first * n..(last + 1) * n {
+            let start = y == 0 && x == first * n;
+            content.push(if !start && rng.chance(8) { '#' } else { '.' });
+        }
+        content.push('\n');
+    }
+    content.push('\n');
+    for i in 0..size {
+        if i > 0 {
+            content.push(rng.letter("LR"));
+        }
+        content += &rng.range(1..=50).to_string();
+    }
+    content.push('\n');
+    Ok(content)
+}
+
+fn day_23(rng: &mut Rng, size: usize, _: &()) -> String {
+    (0..size)
+        .map(|_| {
+            let mut row = (0..size).map(|_| rng.letter("#.")).collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+common::params! {
+    /// Day-24 generator parameters
+    pub struct Basin {
+        /// Rows of the valley
+        pub height: usize = 35,
+        /// Percentage of the valley positions with a blizzard
+        pub density: u32 = 90,
+    }
+}
+
+// No blizzard going up or down in the entrance and exit columns, as in the
+// puzzle inputs. Valleys are drawn until the three crossings have a way.
+fn day_24(rng: &mut Rng, size: usize, params: &Basin) -> Result<String, String> {
+    if params.height < 1 || params.density > 100 {
+        return Err(String::from(
+            "the valley needs a row and at most 100% of blizzards",
+        ));
+    }
+    for _ in 0..20 {
+        let mut content = format!("#.{}\n", "#".repeat(size));
+        for _ in 0..params.height {
+            content.push('#');
+            for x in 0..size {
+                let blizzards = match x == 0 || x == size - 1 {
+                    true => "<>",
+                    false => "<>^v",
+                };
+                let blizzard = rng.chance(params.density);
+                content.push(if blizzard { rng.letter(blizzards) } else { '.' });
+            }
+            content += "#\n";
+        }
+        content += &format!("{}.#\n", "#".repeat(size));
+        let world = day_24::parse(&content).map_err(|e| e.to_string())?;
+        if world.try_total_time(3).is_some() {
+            return Ok(content);
+        }
+    }
+    Err(String::from("no way across 20 valleys, lower the density"))
+}
+
+fn day_25(rng: &mut Rng, size: usize, _: &()) -> Result<String, String> {
+    if size > 90_000 {
+        return Err(String::from(
+            "day 25 has 90000 numbers at most, for the sum to fit in 64 bits",
+        ));
+    }
+    Ok((0..size)
+        .map(|_| {
+            let digits = rng.range(1..=20) as u32;
+            let value = rng.range(5i64.pow(digits - 1)..=5i64.pow(digits) - 1);
+            format!("{}\n", day_25::i64_to_snafu(value))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_inputs() {
+        let small = [
+            5, 20, 9, 20, 20, 100, 20, 10, 50, 6, 4, 10, 10, 10, 8, 12, 200, 100, 2, 50, 21, 20,
+            10, 10, 10,
+        ];
+        for day in 1..=25 {
+            let (gen, run) = match day {
+                15 => ("max = 2000", "max = 2000\nrow = 1000"),
+                16 => ("valves = 5", ""),
+                19 => ("", "minutes1 = 16\nminutes2 = 16"),
+                24 => ("height = 5\ndensity = 40", ""),
+                _ => ("", ""),
+            };
+            let gen = Settings::parse(gen).unwrap();
+            let run = Settings::parse(run).unwrap();
+            let size = Some(small[day as usize - 1]);
+            let content = generate(day, size, 1, &gen).unwrap();
+            assert_eq!(generate(day, size, 1, &gen).unwrap(), content);
+            assert_ne!(generate(day, size, 2, &gen).unwrap(), content);
+            let runs = crate::run(day, &[1, 2], &content, &run).unwrap();
+            assert!(runs.is_ok(), "day {}: {}", day, runs.err().unwrap());
+        }
+    }
+
+    #[test]
+    fn default_inputs() {
+        let none = Settings::default();
+        for seed in 0..20 {
+            let content = generate(7, None, seed, &none).unwrap();
+            let answers = crate::run(7, &[1], &content, &none).unwrap().unwrap();
+            assert_ne!(answers[0].answer, "0", "day 7, seed {}", seed);
+            let content = generate(14, None, seed, &none).unwrap();
+            let answers = crate::run(14, &[1, 2], &content, &none).unwrap().unwrap();
+            let (part1, part2) = (&answers[0].answer, &answers[1].answer);
+            let (part1, part2) = (part1.parse::<u32>(), part2.parse::<u32>());
+            assert!(part1.unwrap() < part2.unwrap(), "day 14, seed {}", seed);
+        }
+        let gen = Settings::parse("max = 2000").unwrap();
+        let run = Settings::parse("max = 2000\nrow = 1000").unwrap();
+        for seed in 0..20 {
+            let content = generate(15, None, seed, &gen).unwrap();
+            let runs = crate::run(15, &[1, 2], &content, &run).unwrap();
+            assert!(
+                runs.is_ok(),
+                "day 15, seed {}: {}",
+                seed,
+                runs.err().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn cube_nets() {
+        let nets = (1..=11).map(|i| i.to_string());
+        for layout in nets.chain(["input", "example"].map(String::from)) {
+            for (face, seed) in [(1, 0), (4, 1), (10, 2), (50, 3)] {
+                let gen = format!("face = {}\nlayout = \"{}\"", face, layout);
+                let gen = Settings::parse(&gen).unwrap();
+                let content = generate(22, Some(100), seed, &gen).unwrap();
+                let runs = crate::run(22, &[1, 2], &content, &Settings::default()).unwrap();
+                assert!(
+                    runs.is_ok(),
+                    "{} of {}, seed {}: {}",
+                    layout,
+                    face,
+                    seed,
+                    runs.err().unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn generator_errors() {
+        let none = Settings::default();
+        assert!(generate(6, Some(10), 0, &none).is_err());
+        assert!(generate(11, Some(10), 0, &none).is_err());
+        assert!(generate(1, None, 0, &Settings::parse("max = 3").unwrap()).is_err());
+        let layout = Settings::parse("layout = \"cross\"").unwrap();
+        assert!(generate(22, None, 0, &layout).is_err());
+        let layout = Settings::parse("layout = \"12\"").unwrap();
+        assert!(generate(22, None, 0, &layout).is_err());
+    }
+}
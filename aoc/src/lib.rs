@@ -0,0 +1,453 @@
+use std::{
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Once},
+    thread,
+    time::{Duration, Instant},
+};
+
+use common::{
+    budget::{self, Abort, Budget},
+    checkpoint, counters, explain, Error, Image, Parts, Replay, Settings,
+};
+
+pub mod answers;
+pub mod export;
+pub mod gen;
+pub mod player;
+pub mod server;
+
+/// Result of solving one part of a day.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    /// Wall time of the part.
+    pub time: Duration,
+    /// Wall time of the parse, shared by the parts of a same run.
+    pub parse_time: Duration,
+    /// Internal counters of the solver, see `common::counters`.
+    pub counters: Vec<(String, u64)>,
+    /// Why the part stopped before proving its answer, the best found so far,
+    /// see `common::budget`.
+    pub aborted: Option<Abort>,
+    /// Steps of the derivation of the answer in explain mode, see
+    /// `common::explain`.
+    pub explanation: Vec<String>,
+}
+
+impl Run {
+    /// Single line JSON object of the run.
+    pub fn to_json(&self) -> String {
+        let mut counters = json::JsonValue::new_object();
+        for (name, value) in self.counters.iter() {
+            counters[name.as_str()] = (*value).into();
+        }
+        let mut js = json::object! {
+            day: self.day,
+            part: self.part,
+            answer: self.answer.as_str(),
+            proven: self.aborted.is_none(),
+            time_ms: self.time.as_secs_f64() * 1000.,
+            parse_ms: self.parse_time.as_secs_f64() * 1000.,
+            counters: counters,
+        };
+        if let Some(abort) = self.aborted {
+            js["aborted"] = abort.to_string().into();
+        }
+        if !self.explanation.is_empty() {
+            js["explanation"] = self.explanation.clone().into();
+        }
+        js.dump()
+    }
+}
+
+/// Parse the input once and solve the given parts of a day with the parameters
+/// of `settings`, timing each step and collecting the solver counters of each part.
+/// Each part has the budget and the checkpoint set for the thread by
+/// `common::budget::set` and `common::checkpoint::set`, and is explained in
+/// the explain mode of the thread ( `common::explain::set` ).
+/// Returns None if the day is not one of the 25 days.
+pub fn run(
+    day: u32,
+    parts: &[u8],
+    content: &str,
+    settings: &Settings,
+) -> Option<Result<Vec<Run>, Error>> {
+    let start = Instant::now();
+    let solver = match prepare(day, content, settings)? {
+        Ok(solver) => solver,
+        Err(e) => return Some(Err(e)),
+    };
+    let parse_time = start.elapsed();
+    let runs = parts
+        .iter()
+        .map(|&part| {
+            counters::take();
+            explain::take();
+            budget::start();
+            checkpoint::start(part);
+            let start = Instant::now();
+            let answer = solver(part)?;
+            Ok(Run {
+                day,
+                part,
+                answer,
+                time: start.elapsed(),
+                parse_time,
+                counters: counters::take(),
+                aborted: budget::take(),
+                explanation: explain::take(),
+            })
+        })
+        .collect();
+    Some(runs)
+}
+
+/// Solve one part of a day from the input content.
+/// Returns None if the day is not one of the 25 days.
+pub fn solve(
+    day: u32,
+    part: u8,
+    content: &str,
+    settings: &Settings,
+) -> Option<Result<String, Error>> {
+    Some(prepare(day, content, settings)?.and_then(|solver| solver(part)))
+}
+
+/// How a part run by `run_all` ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Solved(String),
+    /// Best answer found before running out of budget
+    Unproven(String, Abort),
+    /// Unreadable input, parse or parameter error
+    Error(String),
+    /// Panic message and location
+    Panicked(String),
+}
+
+/// A part run by `run_all` in its own thread.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub day: u32,
+    pub part: u8,
+    /// Wall time of the part, parse included.
+    pub time: Duration,
+    pub status: Status,
+}
+
+// Some days recurse deeply, past the 2 MB default stack of a thread
+const STACK_SIZE: usize = 64 * 1024 * 1024;
+const WORKER: &str = "aoc-worker";
+
+thread_local! {
+    // Last panic of a worker thread, recorded by the panic hook
+    static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static HOOK: Once = Once::new();
+
+// Records the panics of the worker threads, the other threads keep the
+// previous hook. Set once, run_all may run in several threads at a time.
+fn record_panics() {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() == Some(WORKER) {
+            let message = info.payload_as_str().unwrap_or("panic");
+            let message = match info.location() {
+                Some(location) => format!("{} at {}", message, location),
+                None => message.to_string(),
+            };
+            PANIC.with(|panic| *panic.borrow_mut() = Some(message));
+        } else {
+            hook(info);
+        }
+    }));
+}
+
+/// Solves the parts of the days, each part in its own thread with its
+/// `budget`, `read` giving the input and settings of a day. A panic in a part
+/// is reported in its outcome without stopping the others. Outcomes are sorted
+/// by day and part.
+pub fn run_all<F>(days: &[u32], parts: &[u8], budget: Budget, read: F) -> Vec<Outcome>
+where
+    F: Fn(u32) -> Result<(String, Settings), String>,
+{
+    HOOK.call_once(record_panics);
+    let mut outcomes = vec![];
+    let mut workers = vec![];
+    for &day in days {
+        let input = read(day).map(|(content, settings)| (Arc::new(content), Arc::new(settings)));
+        for &part in parts {
+            let (content, settings) = match &input {
+                Ok((content, settings)) => (content.clone(), settings.clone()),
+                Err(e) => {
+                    outcomes.push(Outcome {
+                        day,
+                        part,
+                        time: Duration::ZERO,
+                        status: Status::Error(e.clone()),
+                    });
+                    continue;
+                }
+            };
+            let worker = thread::Builder::new()
+                .name(String::from(WORKER))
+                .stack_size(STACK_SIZE)
+                .spawn(move || {
+                    budget::set(budget);
+                    run_part(day, part, &content, &settings)
+                })
+                .expect("cannot start a worker thread");
+            workers.push(worker);
+        }
+    }
+    outcomes.extend(
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("worker panics are caught")),
+    );
+    outcomes.sort_by_key(|outcome| (outcome.day, outcome.part));
+    outcomes
+}
+
+impl Status {
+    /// Short name of the status: `ok`, `unproven`, `error` or `panic`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Solved(_) => "ok",
+            Status::Unproven(..) => "unproven",
+            Status::Error(_) => "error",
+            Status::Panicked(_) => "panic",
+        }
+    }
+}
+
+impl Outcome {
+    /// Single line JSON object of the outcome, with the answer or the error.
+    pub fn to_json(&self) -> String {
+        let mut js = json::object! {
+            day: self.day,
+            part: self.part,
+            status: self.status.name(),
+            time_ms: self.time.as_secs_f64() * 1000.,
+        };
+        match &self.status {
+            Status::Solved(answer) => js["answer"] = answer.as_str().into(),
+            Status::Unproven(answer, abort) => {
+                js["answer"] = answer.as_str().into();
+                js["aborted"] = abort.to_string().into();
+            }
+            Status::Error(e) | Status::Panicked(e) => js["error"] = e.as_str().into(),
+        }
+        js.dump()
+    }
+}
+
+fn run_part(day: u32, part: u8, content: &str, settings: &Settings) -> Outcome {
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| run(day, &[part], content, settings)));
+    let status = match result {
+        Ok(Some(Ok(mut runs))) => {
+            let run = runs.remove(0);
+            match run.aborted {
+                Some(abort) => Status::Unproven(run.answer, abort),
+                None => Status::Solved(run.answer),
+            }
+        }
+        Ok(Some(Err(e))) => Status::Error(e.to_string()),
+        Ok(None) => Status::Error(format!("no day {}", day)),
+        Err(_) => Status::Panicked(PANIC.with(|panic| panic.take()).unwrap_or_default()),
+    };
+    Outcome {
+        day,
+        part,
+        time: start.elapsed(),
+        status,
+    }
+}
+
+// Calls a generic function of `common` with the solution of a day, returns
+// None from the calling function if the day is not one of the 25 days.
+macro_rules! with_solution {
+    ($day:expr, $f:ident($($arg:expr),*)) => {
+        match $day {
+            1 => common::$f::<day_01::Solution>($($arg),*),
+            2 => common::$f::<day_02::Solution>($($arg),*),
+            3 => common::$f::<day_03::Solution>($($arg),*),
+            4 => common::$f::<day_04::Solution>($($arg),*),
+            5 => common::$f::<day_05::Solution>($($arg),*),
+            6 => common::$f::<day_06::Solution>($($arg),*),
+            7 => common::$f::<day_07::Solution>($($arg),*),
+            8 => common::$f::<day_08::Solution>($($arg),*),
+            9 => common::$f::<day_09::Solution>($($arg),*),
+            10 => common::$f::<day_10::Solution>($($arg),*),
+            11 => common::$f::<day_11::Solution>($($arg),*),
+            12 => common::$f::<day_12::Solution>($($arg),*),
+            13 => common::$f::<day_13::Solution>($($arg),*),
+            14 => common::$f::<day_14::Solution>($($arg),*),
+            15 => common::$f::<day_15::Solution>($($arg),*),
+            16 => common::$f::<day_16::Solution>($($arg),*),
+            17 => common::$f::<day_17::Solution>($($arg),*),
+            18 => common::$f::<day_18::Solution>($($arg),*),
+            19 => common::$f::<day_19::Solution>($($arg),*),
+            20 => common::$f::<day_20::Solution>($($arg),*),
+            21 => common::$f::<day_21::Solution>($($arg),*),
+            22 => common::$f::<day_22::Solution>($($arg),*),
+            23 => common::$f::<day_23::Solution>($($arg),*),
+            24 => common::$f::<day_24::Solution>($($arg),*),
+            25 => common::$f::<day_25::Solution>($($arg),*),
+            _ => return None,
+        }
+    };
+}
+
+/// Parse the input of a day and set its parameters, to then solve its parts.
+/// Returns None if the day is not one of the 25 days.
+pub fn prepare(day: u32, content: &str, settings: &Settings) -> Option<Result<Parts, Error>> {
+    Some(with_solution!(day, prepare(content, settings)))
+}
+
+/// Parse the input of a day and set its parameters, to then replay the
+/// simulation of a part in the terminal player. None inside for the days
+/// without simulation.
+/// Returns None if the day is not one of the 25 days.
+pub fn replay(
+    day: u32,
+    part: u8,
+    content: &str,
+    settings: &Settings,
+) -> Option<Result<Option<Replay>, Error>> {
+    Some(with_solution!(day, replay(content, settings, part)))
+}
+
+/// Parse the input of a day and set its parameters, to then draw the named
+/// images of a part, empty for the days without.
+/// Returns None if the day is not one of the 25 days.
+pub fn images(
+    day: u32,
+    part: u8,
+    content: &str,
+    settings: &Settings,
+) -> Option<Result<Vec<(&'static str, Image)>, Error>> {
+    Some(with_solution!(day, images(content, settings, part)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_json() {
+        let content = include_str!("../../day-16/example.txt");
+        let runs = run(16, &[1, 2], content, &Settings::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[1].answer, "1707");
+        let js = json::parse(&runs[0].to_json()).unwrap();
+        assert_eq!(js["day"], 16);
+        assert_eq!(js["part"], 1);
+        assert_eq!(js["answer"], "1651");
+        assert!(js["time_ms"].as_f64().unwrap() > 0.);
+        assert!(js["parse_ms"].as_f64().unwrap() > 0.);
+        assert!(js["counters"]["iterations"].as_u64().unwrap() > 0);
+    }
+
+    #[test]
+    fn run_params() {
+        let content = include_str!("../../day-15/example.txt");
+        let config = Settings::parse(include_str!("../../day-15/example.toml")).unwrap();
+        let runs = run(15, &[1, 2], content, &config).unwrap().unwrap();
+        assert_eq!(runs[0].answer, "26");
+        assert_eq!(runs[1].answer, "56000011");
+        let mut args = vec![String::from("--param"), String::from("row=11")];
+        let row_11 = config.merge(&Settings::take_args(&mut args).unwrap());
+        assert_eq!(solve(15, 1, content, &row_11).unwrap().unwrap(), "28");
+
+        let content = include_str!("../../day-16/example.txt");
+        let start = Settings::parse("start = \"ZZ\"").unwrap();
+        assert!(matches!(
+            run(16, &[1], content, &start),
+            Some(Err(Error::Param(_)))
+        ));
+    }
+
+    #[test]
+    fn run_budget() {
+        let content = include_str!("../../day-16/example.txt");
+        budget::set(Budget {
+            timeout: Some(Duration::ZERO),
+            max_memory: None,
+        });
+        let runs = run(16, &[2], content, &Settings::default()).unwrap();
+        budget::set(Budget::default());
+        let run = &runs.unwrap()[0];
+        assert_eq!(run.aborted, Some(Abort::Timeout));
+        assert!(run.answer.parse::<u32>().unwrap() <= 1707);
+        let js = json::parse(&run.to_json()).unwrap();
+        assert_eq!(
+            (js["proven"].as_bool(), js["aborted"].as_str()),
+            (Some(false), Some("timeout"))
+        );
+
+        let read = |_| Ok((String::from(content), Settings::default()));
+        let budget = Budget {
+            timeout: Some(Duration::ZERO),
+            max_memory: None,
+        };
+        let outcomes = run_all(&[16], &[2], budget, read);
+        let answer = run.answer.clone();
+        assert_eq!(outcomes[0].status, Status::Unproven(answer, Abort::Timeout));
+    }
+
+    #[test]
+    fn run_explained() {
+        let content = include_str!("../../day-21/example.txt");
+        let runs = run(21, &[1], content, &Settings::default())
+            .unwrap()
+            .unwrap();
+        assert!(runs[0].explanation.is_empty());
+        assert!(!json::parse(&runs[0].to_json())
+            .unwrap()
+            .has_key("explanation"));
+        explain::set(true);
+        let runs = run(21, &[1, 2], content, &Settings::default())
+            .unwrap()
+            .unwrap();
+        explain::set(false);
+        assert_eq!(runs[0].explanation, ["root: pppw + sjmn = 2 + 150 = 152"]);
+        let js = json::parse(&runs[1].to_json()).unwrap();
+        assert_eq!(js["explanation"][0], "root: pppw = sjmn = 150");
+        assert_eq!(js["explanation"][4], "ptdq: humn - 3 = 298, so humn = 301");
+    }
+
+    #[test]
+    fn run_all_parts() {
+        let read = |day| match day {
+            1 => Ok((String::from("1\n2\n\n4\n"), Settings::default())),
+            // No way up from `b` to `E`
+            12 => Ok((String::from("SbE\n"), Settings::default())),
+            16 => Ok((String::from("Valve"), Settings::default())),
+            _ => Err(String::from("no input")),
+        };
+        let outcomes = run_all(&[16, 12, 1, 2], &[1, 2], Budget::default(), read);
+        let statuses = outcomes
+            .iter()
+            .map(|o| (o.day, o.part, o.status.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(statuses[0], (1, 1, Status::Solved(String::from("4"))));
+        assert_eq!(statuses[1], (1, 2, Status::Solved(String::from("7"))));
+        assert_eq!(statuses[2], (2, 1, Status::Error(String::from("no input"))));
+        assert!(matches!(
+            &statuses[4],
+            (12, 1, Status::Panicked(message))
+                if message.starts_with("no path from a start to the end")
+                    && message.contains("day-12/src/lib.rs")
+        ));
+        assert!(matches!(&statuses[7], (16, 2, Status::Error(_))));
+        assert_eq!(statuses.len(), 8);
+    }
+}
@@ -0,0 +1,665 @@
+use std::{
+    collections::BTreeSet,
+    env, fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    net::TcpListener,
+    path::PathBuf,
+    process, thread,
+    time::{Duration, Instant},
+};
+
+use aoc::{
+    answers::{Answers, Verdict},
+    export, server, Status,
+};
+use common::{
+    budget::{self, Abort, Budget, CountingAllocator},
+    checked, checkpoint, explain, logger, parse_parts, Error, Settings, Source,
+};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "\
+Usage: aoc run <day> <1|2|both> [FILE|-|--example [N]] [--param NAME=VALUE]...
+               [--format text|json] [--timeout T] [--max-memory M] [-v|-vv]
+               [--checkpoint FILE [--resume]] [--checked] [--explain]
+       aoc run all [1|2|both] [--example [N]] [--format text|json]
+                   [--timeout T] [--max-memory M]
+       aoc verify [DAY...] [--answers FILE] [--timeout T] [--max-memory M] [-v|-vv]
+       aoc gen <day> [--size N] [--seed S] [--param NAME=VALUE]...
+       aoc play <day> <1|2> [FILE|-|--example [N]] [--param NAME=VALUE]...
+       aoc image <day> <1|2> [FILE|-|--example [N]] [--param NAME=VALUE]...
+                 [--out DIR] [--every N] [--frames N] [--scale N]
+       aoc serve [ADDRESS] [--timeout T] [--max-memory M] [-v|-vv]";
+const ANSWERS: &str = "answers.toml";
+// Defaults of `aoc serve`
+const SERVE_ADDRESS: &str = "127.0.0.1:2022";
+const SERVE_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_CONNECTIONS: usize = 32;
+const MAX_INPUT: usize = 4 << 20;
+// Time between two saves of a checkpoint
+const CHECKPOINT_EVERY: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// Where `run --checkpoint` saves the searches.
+struct Checkpoint {
+    file: PathBuf,
+    /// Continue the searches saved in the file
+    resume: bool,
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+fn main() {
+    let mut args = vec![];
+    let mut format = Format::Text;
+    let mut answers = String::from(ANSWERS);
+    let mut budget = Budget::default();
+    let mut checkpoint = None;
+    let mut resume = false;
+    let mut checked = false;
+    let mut explain = false;
+    let mut all_args = env::args().skip(1);
+    while let Some(arg) = all_args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match all_args.next().as_deref() {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    _ => usage_error("--format must be followed by text or json"),
+                }
+            }
+            "--answers" => match all_args.next() {
+                Some(file) => answers = file,
+                None => usage_error("--answers must be followed by a file"),
+            },
+            "--timeout" => match all_args.next().as_deref().and_then(parse_duration) {
+                Some(timeout) => budget.timeout = Some(timeout),
+                None => {
+                    usage_error("--timeout must be followed by a duration, like 10s, 500ms or 2m")
+                }
+            },
+            "--max-memory" => match all_args.next().as_deref().and_then(parse_size) {
+                Some(size) => budget.max_memory = Some(size),
+                None => usage_error("--max-memory must be followed by a size, like 512M or 2G"),
+            },
+            "--checkpoint" => match all_args.next() {
+                Some(file) => checkpoint = Some(PathBuf::from(file)),
+                None => usage_error("--checkpoint must be followed by a file"),
+            },
+            "--resume" => resume = true,
+            "--checked" => checked = true,
+            "--explain" => explain = true,
+            _ => args.push(arg),
+        }
+    }
+    logger::init(logger::verbosity(&mut args));
+    let example = Source::take_example(&mut args);
+    let overrides = Settings::take_args(&mut args).unwrap_or_else(|e| usage_error(&e));
+    let verified = example.is_none() && overrides == Settings::default();
+    let command = args.first().map(|c| c.as_str());
+    if budget != Budget::default() && !matches!(command, Some("run" | "verify" | "serve")) {
+        usage_error("--timeout and --max-memory are for the run, verify and serve commands only");
+    }
+    let single_run = command == Some("run") && args.get(1).is_some_and(|a| a != "all");
+    if checked && !single_run {
+        usage_error("--checked is for the run command of a single day");
+    }
+    if explain && !single_run {
+        usage_error("--explain is for the run command of a single day");
+    }
+    explain::set(explain);
+    let checkpoint = match (checkpoint, resume) {
+        (Some(_), _) if !single_run => {
+            usage_error("--checkpoint is for the run command of a single day")
+        }
+        (None, true) => usage_error("--resume needs the --checkpoint file to resume from"),
+        (file, resume) => file.map(|file| Checkpoint { file, resume }),
+    };
+    match command {
+        Some("run") => run(
+            &args, example, &overrides, format, budget, checkpoint, checked,
+        ),
+        Some("verify") if verified => verify(&args, &answers, budget),
+        Some("verify") => usage_error("verify runs the inputs with their own parameters only"),
+        Some("gen") if example.is_none() => gen(&args, &overrides),
+        Some("play") => play(&args, example, &overrides),
+        Some("image") => image(&args, example, &overrides),
+        Some("serve") if verified => serve(&args, budget),
+        Some("serve") => usage_error("serve takes the inputs and parameters of its requests"),
+        _ => usage_error("Expecting a run, verify, gen, play, image or serve command"),
+    }
+}
+
+/// Duration like `10s`, `500ms`, `2m`, in seconds without unit.
+fn parse_duration(arg: &str) -> Option<Duration> {
+    let (value, unit) = match arg.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(i) => arg.split_at(i),
+        None => (arg, ""),
+    };
+    let value = value.parse::<f64>().ok()?;
+    let seconds = match unit {
+        "" | "s" => value,
+        "ms" => value / 1000.,
+        "m" => value * 60.,
+        "h" => value * 3600.,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Size like `512M`, `2G`, `64K`, in bytes without unit.
+fn parse_size(arg: &str) -> Option<usize> {
+    let (value, unit) = match arg.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(i) => arg.split_at(i),
+        None => (arg, ""),
+    };
+    let factor = match unit {
+        "" | "B" => 1,
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        _ => return None,
+    };
+    value.parse::<usize>().ok()?.checked_mul(factor)
+}
+
+fn parse_day(arg: &str) -> u32 {
+    match arg.parse::<u32>() {
+        Ok(day) if (1..=25).contains(&day) => day,
+        _ => usage_error(&format!("day must be between 1 and 25, not {}", arg)),
+    }
+}
+
+/// File name, content and settings of the input of a day, from its argument
+/// or the example option, exits on error.
+fn read_input(
+    day: u32,
+    arg: Option<&String>,
+    example: Option<Source>,
+    overrides: &Settings,
+) -> (String, String, Settings) {
+    let source = match arg {
+        Some(arg) => Source::from_arg(arg),
+        None => example.unwrap_or(Source::Input),
+    };
+    let (filename, content) = match source.read(&format!("day-{:02}", day)) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    match Settings::config(&filename) {
+        Ok(config) => (filename, content, config.merge(overrides)),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn run(
+    args: &[String],
+    example: Option<Source>,
+    overrides: &Settings,
+    format: Format,
+    budget: Budget,
+    checkpoint: Option<Checkpoint>,
+    checked: bool,
+) {
+    if args.get(1).map(|a| a.as_str()) == Some("all") {
+        return run_all(args, example, overrides, format, budget);
+    }
+    if args.len() < 3 || args.len() > 4 || (args.len() == 4 && example.is_some()) {
+        usage_error("Expecting a day, a part and an input to run");
+    }
+    let day = parse_day(&args[1]);
+    if checkpoint.is_some() && ![16, 19].contains(&day) {
+        usage_error("only days 16 and 19 have checkpoints");
+    }
+    let parts = match parse_parts(&args[2]) {
+        Some(parts) => parts,
+        None => usage_error(&format!("part must be 1, 2 or both, not {}", args[2])),
+    };
+    let (filename, content, settings) = read_input(day, args.get(3), example, overrides);
+    budget::set(budget);
+    if let Some(checkpoint) = &checkpoint {
+        let tag = checkpoint_tag(day, &content, &settings);
+        let resume = checkpoint.resume;
+        if let Err(e) = checkpoint::set(&checkpoint.file, &tag, CHECKPOINT_EVERY, resume) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+        // Ctrl-C stops the searches, saving them, a second one exits. The
+        // handler only sets atomics and calls _exit, both signal safe.
+        let handler = || {
+            if budget::interrupt() {
+                signal_hook::low_level::exit(130);
+            }
+        };
+        unsafe { signal_hook::low_level::register(signal_hook::consts::SIGINT, handler) }
+            .expect("cannot handle Ctrl-C");
+    }
+    checked::set(checked);
+    let result = checked::catch(|| aoc::run(day, &parts, &content, &settings).unwrap());
+    let result = result.unwrap_or_else(|overflow| {
+        eprintln!("Day {:02}: overflow, {}", day, overflow);
+        process::exit(1);
+    });
+    let runs = match result {
+        Ok(runs) => runs,
+        Err(Error::Param(e)) => usage_error(&e),
+        Err(Error::Parse(e)) => {
+            eprint!("{}", e.in_file(&filename).diagnostic(&content));
+            process::exit(1);
+        }
+    };
+    for run in runs.iter() {
+        match format {
+            Format::Text => {
+                match run.aborted {
+                    Some(abort) => println!(
+                        "Day {:02} part {}: {} ( unproven: {} )",
+                        day, run.part, run.answer, abort
+                    ),
+                    None => println!("Day {:02} part {}: {}", day, run.part, run.answer),
+                }
+                for step in run.explanation.iter() {
+                    println!("  {}", step);
+                }
+            }
+            Format::Json => println!("{}", run.to_json()),
+        }
+    }
+    if let (Some(checkpoint), true) = (&checkpoint, runs.iter().any(|r| r.aborted.is_some())) {
+        eprintln!(
+            "Searches saved to {}, continue them with --resume",
+            checkpoint.file.display()
+        );
+    }
+    if runs.iter().any(|r| r.aborted == Some(Abort::Interrupted)) {
+        process::exit(130);
+    }
+}
+
+/// Day, input and parameters of a checkpoint.
+fn checkpoint_tag(day: u32, content: &str, settings: &Settings) -> String {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    format!("{:?}", settings).hash(&mut hasher);
+    format!("day-{:02} {:016x}", day, hasher.finish())
+}
+
+fn run_all(
+    args: &[String],
+    example: Option<Source>,
+    overrides: &Settings,
+    format: Format,
+    budget: Budget,
+) {
+    if args.len() > 3 {
+        usage_error("Expecting a part to run all the days, on their day-XX/input.txt or examples");
+    }
+    if *overrides != Settings::default() {
+        usage_error("--param is for a single day, the days run with their own parameters");
+    }
+    let parts = match args.get(2).map(|p| (p, parse_parts(p))) {
+        None => vec![1, 2],
+        Some((_, Some(parts))) => parts,
+        Some((p, None)) => usage_error(&format!("part must be 1, 2 or both, not {}", p)),
+    };
+    let source = example.unwrap_or(Source::Input);
+    let start = Instant::now();
+    let outcomes = aoc::run_all(&(1..=25).collect::<Vec<_>>(), &parts, budget, |day| {
+        let (filename, content) = source.read(&format!("day-{:02}", day))?;
+        Ok((content, Settings::config(&filename)?))
+    });
+    let wall_time = start.elapsed();
+    if format == Format::Text {
+        println!(
+            "{:<6} {:>4}  {:<6} {:>10}  answer",
+            "day", "part", "status", "time"
+        );
+    }
+    let (mut solved, mut unproven, mut errors, mut panics) = (0, 0, 0, 0);
+    for outcome in outcomes.iter() {
+        let (status, text) = match &outcome.status {
+            Status::Solved(answer) => {
+                solved += 1;
+                ("ok", answer.clone())
+            }
+            Status::Unproven(answer, abort) => {
+                unproven += 1;
+                ("unproven", format!("{} ( {} )", answer, abort))
+            }
+            Status::Error(e) => {
+                errors += 1;
+                ("error", e.clone())
+            }
+            Status::Panicked(message) => {
+                panics += 1;
+                ("PANIC", message.clone())
+            }
+        };
+        match format {
+            Format::Text => println!(
+                "{:<6} {:>4}  {:<6} {:>10}  {}",
+                format!("day-{:02}", outcome.day),
+                outcome.part,
+                status,
+                format!("{:.1?}", outcome.time),
+                one_line(&text)
+            ),
+            Format::Json => println!("{}", outcome.to_json()),
+        }
+    }
+    if format == Format::Text {
+        let parts_time = outcomes.iter().map(|o| o.time).sum::<Duration>();
+        println!(
+            "{} solved, {} unproven, {} errors, {} panics in {:.1?} wall time ( {:.1?} for the parts )",
+            solved, unproven, errors, panics, wall_time, parts_time
+        );
+    }
+    if errors + panics > 0 {
+        process::exit(1);
+    }
+}
+
+fn serve(args: &[String], budget: Budget) {
+    if args.len() > 2 {
+        usage_error("Expecting the address to listen on, like 127.0.0.1:2022");
+    }
+    let address = args.get(1).map_or(SERVE_ADDRESS, |a| a.as_str());
+    let listener = TcpListener::bind(address).unwrap_or_else(|e| {
+        eprintln!("Cannot listen on {}: {}", address, e);
+        process::exit(1);
+    });
+    let options = server::Options {
+        budget: Budget {
+            timeout: budget.timeout.or(Some(SERVE_TIMEOUT)),
+            ..budget
+        },
+        max_body: MAX_INPUT,
+        max_solves: thread::available_parallelism().map_or(1, |n| n.get()),
+        max_connections: MAX_CONNECTIONS,
+    };
+    eprintln!(
+        "Solving POST /day/{{n}}/part/{{p}} on http://{}",
+        listener
+            .local_addr()
+            .map_or(address.to_string(), |a| a.to_string())
+    );
+    server::serve(&listener, options);
+}
+
+/// Input files of a day: its `input*.txt` files, and the recorded ones.
+fn input_files(day: u32, answers: &Answers) -> BTreeSet<String> {
+    let dir = format!("day-{:02}", day);
+    let mut files = answers
+        .files(day)
+        .map(|f| f.to_string())
+        .collect::<BTreeSet<_>>();
+    if let Ok(entries) = fs::read_dir(&dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with("input") && name.ends_with(".txt") {
+                files.insert(format!("{}/{}", dir, name));
+            }
+        }
+    }
+    files
+}
+
+// First line of multi-line answers
+fn one_line(answer: &str) -> String {
+    match answer.trim_end().split_once('\n') {
+        Some((first, _)) => format!("{} ...", first),
+        None => answer.to_string(),
+    }
+}
+
+fn verify(args: &[String], answers_file: &str, budget: Budget) {
+    let days = if args.len() > 1 {
+        args[1..].iter().map(|d| parse_day(d)).collect::<Vec<_>>()
+    } else {
+        (1..=25).collect()
+    };
+    let answers = match fs::read_to_string(answers_file) {
+        Ok(content) => match Answers::parse(&content) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Cannot parse '{}': {}", answers_file, e);
+                process::exit(1);
+            }
+        },
+        Err(e) => {
+            eprintln!("Cannot read '{}': {}, no answer recorded", answers_file, e);
+            Answers::default()
+        }
+    };
+    budget::set(budget);
+    let (mut passed, mut failed, mut unrecorded, mut aborted) = (0, 0, 0, 0);
+    println!(
+        "{:<20} {:>4}  {:<6} {:>10}  answer",
+        "input", "part", "status", "time"
+    );
+    for day in days {
+        let files = input_files(day, &answers);
+        if files.is_empty() {
+            println!("{:<20} {:>4}  {:<6}", format!("day-{:02}", day), "", "none");
+            unrecorded += 1;
+        }
+        for file in files {
+            let content = match fs::read_to_string(&file) {
+                Ok(content) => content,
+                Err(e) => {
+                    println!("{:<20} {:>4}  {:<6} {:>10}  {}", file, "", "error", "", e);
+                    failed += 1;
+                    continue;
+                }
+            };
+            let runs = match Settings::config(&file)
+                .map_err(Error::Param)
+                .and_then(|settings| aoc::run(day, &[1, 2], &content, &settings).unwrap())
+            {
+                Ok(runs) => runs,
+                Err(e) => {
+                    println!("{:<20} {:>4}  {:<6} {:>10}  {}", file, "", "error", "", e);
+                    failed += 1;
+                    continue;
+                }
+            };
+            for run in runs {
+                let part = run.part;
+                let verdict = match run.aborted {
+                    // The best answer so far is not the answer, it cannot be checked
+                    Some(abort) => Err(abort),
+                    None => Ok(answers.check(&file, part, &run.answer)),
+                };
+                let (status, answer) = match verdict {
+                    Err(abort) => {
+                        aborted += 1;
+                        ("abort", format!("no answer ( {} )", abort))
+                    }
+                    Ok(Verdict::Pass) => {
+                        passed += 1;
+                        ("pass", one_line(&run.answer))
+                    }
+                    Ok(Verdict::Fail(expected)) => {
+                        failed += 1;
+                        let answer = one_line(&run.answer);
+                        (
+                            "FAIL",
+                            format!("{} ( expected {} )", answer, one_line(&expected)),
+                        )
+                    }
+                    Ok(Verdict::Unrecorded) => {
+                        unrecorded += 1;
+                        ("new", one_line(&run.answer))
+                    }
+                };
+                let time = format!("{:.1?}", run.time);
+                println!(
+                    "{:<20} {:>4}  {:<6} {:>10}  {}",
+                    file, part, status, time, answer
+                );
+            }
+        }
+    }
+    println!(
+        "{} passed, {} failed, {} without a recorded answer, {} out of budget",
+        passed, failed, unrecorded, aborted
+    );
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn gen(args: &[String], settings: &Settings) {
+    let (mut day, mut size, mut seed) = (None, None, 0);
+    let mut gen_args = args[1..].iter();
+    while let Some(arg) = gen_args.next() {
+        let mut number = |name: &str| match gen_args.next().map(|n| n.parse::<u64>()) {
+            Some(Ok(n)) => n,
+            _ => usage_error(&format!("{} must be followed by a number", name)),
+        };
+        match arg.as_str() {
+            "--size" => size = Some(number("--size") as usize),
+            "--seed" => seed = number("--seed"),
+            _ if day.is_none() => day = Some(parse_day(arg)),
+            _ => usage_error(&format!("unexpected argument {}", arg)),
+        }
+    }
+    let Some(day) = day else {
+        usage_error("Expecting a day to generate");
+    };
+    match aoc::gen::generate(day, size, seed, settings) {
+        Ok(content) => print!("{}", content),
+        Err(e) => usage_error(&e),
+    }
+}
+
+fn play(args: &[String], example: Option<Source>, overrides: &Settings) {
+    if args.len() < 3 || args.len() > 4 || (args.len() == 4 && example.is_some()) {
+        usage_error("Expecting a day, a part and an input to play");
+    }
+    let day = parse_day(&args[1]);
+    let part = match args[2].as_str() {
+        "1" => 1,
+        "2" => 2,
+        _ => usage_error(&format!("part must be 1 or 2, not {}", args[2])),
+    };
+    let (filename, content, settings) = read_input(day, args.get(3), example, overrides);
+    let replay = match aoc::replay(day, part, &content, &settings).unwrap() {
+        Ok(Some(replay)) => replay,
+        Ok(None) => usage_error(&format!(
+            "day {} has no simulation, only days 9, 10, 14, 17, 23 and 24 can be played",
+            day
+        )),
+        Err(Error::Param(e)) => usage_error(&e),
+        Err(Error::Parse(e)) => {
+            eprint!("{}", e.in_file(&filename).diagnostic(&content));
+            process::exit(1);
+        }
+    };
+    let title = format!("Day {:02} part {}  {}", day, part, filename);
+    if let Err(e) = aoc::player::play(replay, &title) {
+        eprintln!("Cannot play in this terminal: {}", e);
+        process::exit(1);
+    }
+}
+
+fn image(args: &[String], example: Option<Source>, overrides: &Settings) {
+    let mut options = export::Options {
+        dir: PathBuf::from("images"),
+        prefix: String::new(),
+        every: 1,
+        frames: 1000,
+        scale: 1,
+    };
+    let mut positional = vec![];
+    let mut image_args = args[1..].iter();
+    while let Some(arg) = image_args.next() {
+        let mut number = |name: &str| match image_args.next().map(|n| n.parse::<usize>()) {
+            Some(Ok(n)) if n > 0 => n,
+            _ => usage_error(&format!("{} must be followed by a positive number", name)),
+        };
+        match arg.as_str() {
+            "--every" => options.every = number("--every"),
+            "--frames" => options.frames = number("--frames"),
+            "--scale" => options.scale = number("--scale"),
+            "--out" => match image_args.next() {
+                Some(dir) => options.dir = PathBuf::from(dir),
+                None => usage_error("--out must be followed by a directory"),
+            },
+            _ => positional.push(arg),
+        }
+    }
+    if positional.len() < 2 || positional.len() > 3 || (positional.len() == 3 && example.is_some())
+    {
+        usage_error("Expecting a day, a part and an input to draw");
+    }
+    let day = parse_day(positional[0]);
+    let part = match positional[1].as_str() {
+        "1" => 1,
+        "2" => 2,
+        _ => usage_error(&format!("part must be 1 or 2, not {}", positional[1])),
+    };
+    let (filename, content, settings) =
+        read_input(day, positional.get(2).copied(), example, overrides);
+    let fail = |e: Error| -> ! {
+        match e {
+            Error::Param(e) => usage_error(&e),
+            Error::Parse(e) => {
+                eprint!("{}", e.in_file(&filename).diagnostic(&content));
+                process::exit(1);
+            }
+        }
+    };
+    options.prefix = format!("day-{:02}-part-{}", day, part);
+    let simulation = aoc::replay(day, part, &content, &settings)
+        .unwrap()
+        .unwrap_or_else(|e| fail(e));
+    let written = match simulation.map(|replay| export::frames(replay(), &options)) {
+        Some(Ok(Some(frames))) => Ok(frames),
+        Some(Err(e)) => Err(e),
+        _ => aoc::images(day, part, &content, &settings)
+            .unwrap()
+            .unwrap_or_else(|e| fail(e))
+            .iter()
+            .map(|(name, image)| options.write(name, image))
+            .collect(),
+    };
+    match written {
+        Ok(written) if written.is_empty() => usage_error(&format!(
+            "day {} has no image, only days 8, 12, 14, 17, 22, 23 and 24 can be drawn",
+            day
+        )),
+        Ok(written) => println!(
+            "{} image(s) written in {}, {} to {}",
+            written.len(),
+            options.dir.display(),
+            written[0].display(),
+            written[written.len() - 1].display()
+        ),
+        Err(e) => {
+            eprintln!(
+                "Cannot write the images in {}: {}",
+                options.dir.display(),
+                e
+            );
+            process::exit(1);
+        }
+    }
+}
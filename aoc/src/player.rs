@@ -0,0 +1,299 @@
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+use common::{Replay, Simulation};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+
+/// A simulation stepped forward, back or to any tick. Going back replays the
+/// simulation from its start.
+pub struct Player {
+    replay: Replay,
+    simulation: Box<dyn Simulation>,
+    tick: usize,
+    end: Option<usize>,
+}
+
+impl Player {
+    pub fn new(replay: Replay) -> Player {
+        let simulation = replay();
+        Player {
+            replay,
+            simulation,
+            tick: 0,
+            end: None,
+        }
+    }
+    pub fn tick(&self) -> usize {
+        self.tick
+    }
+    /// Last tick, once reached.
+    pub fn end(&self) -> Option<usize> {
+        self.end
+    }
+    /// One tick forward, false at the end.
+    pub fn forward(&mut self) -> bool {
+        if self.end == Some(self.tick) {
+            return false;
+        }
+        if self.simulation.step() {
+            self.tick += 1;
+            true
+        } else {
+            self.end = Some(self.tick);
+            false
+        }
+    }
+    pub fn back(&mut self) {
+        self.jump(self.tick.saturating_sub(1));
+    }
+    /// Goes to the tick, or to the end when it is before.
+    pub fn jump(&mut self, tick: usize) {
+        if tick < self.tick {
+            self.simulation = (self.replay)();
+            self.tick = 0;
+        }
+        while self.tick < tick && self.forward() {}
+    }
+    pub fn draw(&self) -> String {
+        self.simulation.draw()
+    }
+    pub fn status(&self) -> String {
+        self.simulation.status()
+    }
+}
+
+// Ticks per second when playing
+const SPEEDS: [u32; 12] = [1, 2, 5, 10, 20, 50, 100, 200, 500, 1000, 5000, 20000];
+const FRAME: Duration = Duration::from_millis(40);
+const HELP: &str = "space play/pause  → step  ← back  + - speed  g jump to tick  r restart  \
+                    ↑ ↓ PgUp PgDn [ ] scroll  q quit";
+
+// Raw mode on the alternate screen, restored when dropped, even on error
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Screen> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Plays a simulation in the terminal until `q`, see `HELP` for the keys.
+pub fn play(replay: Replay, title: &str) -> io::Result<()> {
+    let mut player = Player::new(replay);
+    let _screen = Screen::enter()?;
+    let mut playing = false;
+    let mut speed = 3;
+    // Columns and rows scrolled in the drawing
+    let mut scroll = (0, 0);
+    // Tick typed after `g`
+    let mut jump_to: Option<String> = None;
+    let mut last_tick = Instant::now();
+    loop {
+        let state = match (&jump_to, playing) {
+            (Some(digits), _) => format!(
+                "Jump to tick: {}_  ( Enter to jump, Esc to cancel )",
+                digits
+            ),
+            (None, true) => format!("Playing {} ticks/s", SPEEDS[speed]),
+            (None, false) => format!("Paused, {} ticks/s", SPEEDS[speed]),
+        };
+        let page = draw_frame(&player, title, &state, &mut scroll)?;
+        let timeout = if playing {
+            FRAME
+        } else {
+            Duration::from_secs(1)
+        };
+        if event::poll(timeout)? {
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Some(digits) = jump_to.as_mut() {
+                match key.code {
+                    KeyCode::Char(c) if c.is_ascii_digit() => digits.push(c),
+                    KeyCode::Backspace => {
+                        digits.pop();
+                    }
+                    KeyCode::Enter => {
+                        if let Ok(tick) = digits.parse() {
+                            player.jump(tick);
+                        }
+                        jump_to = None;
+                    }
+                    KeyCode::Esc => jump_to = None,
+                    _ => (),
+                }
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(())
+                }
+                KeyCode::Char(' ') => {
+                    playing = !playing;
+                    last_tick = Instant::now();
+                }
+                KeyCode::Right | KeyCode::Char('.') => {
+                    playing = false;
+                    player.forward();
+                }
+                KeyCode::Left | KeyCode::Char(',') => {
+                    playing = false;
+                    player.back();
+                }
+                KeyCode::Char('+') | KeyCode::Char('=') => {
+                    speed = (speed + 1).min(SPEEDS.len() - 1)
+                }
+                KeyCode::Char('-') => speed = speed.saturating_sub(1),
+                KeyCode::Char('g') => {
+                    playing = false;
+                    jump_to = Some(String::new());
+                }
+                KeyCode::Char('r') | KeyCode::Home => {
+                    playing = false;
+                    player.jump(0);
+                }
+                KeyCode::Up => scroll.1 = scroll.1.saturating_sub(1),
+                KeyCode::Down => scroll.1 += 1,
+                KeyCode::PageUp => scroll.1 = scroll.1.saturating_sub(page),
+                KeyCode::PageDown => scroll.1 += page,
+                KeyCode::Char('[') => scroll.0 = scroll.0.saturating_sub(8),
+                KeyCode::Char(']') => scroll.0 += 8,
+                _ => (),
+            }
+        }
+        if playing {
+            // The ticks due since the last one, as many as a frame allows
+            let tick_time = Duration::from_secs(1) / SPEEDS[speed];
+            let start = Instant::now();
+            while last_tick.elapsed() >= tick_time {
+                if !player.forward() {
+                    playing = false;
+                    break;
+                }
+                last_tick += tick_time;
+                if start.elapsed() > FRAME {
+                    last_tick = Instant::now();
+                    break;
+                }
+            }
+        }
+    }
+}
+
+// Status lines, the visible part of the drawing and the keys, returns the
+// number of drawing rows shown
+fn draw_frame(
+    player: &Player,
+    title: &str,
+    state: &str,
+    scroll: &mut (usize, usize),
+) -> io::Result<usize> {
+    let (width, height) = terminal::size()?;
+    let (width, height) = (width as usize, height as usize);
+    let drawing = player.draw();
+    let rows = drawing.lines().collect::<Vec<_>>();
+    let page = height.saturating_sub(4).max(1);
+    scroll.1 = scroll.1.min(rows.len().saturating_sub(page));
+    let tick = match player.end() {
+        Some(end) => format!("Tick {}/{}", player.tick(), end),
+        None => format!("Tick {}", player.tick()),
+    };
+    let mut lines = vec![
+        format!("{}  {}", title, player.status()),
+        format!("{}  {}", tick, state),
+    ];
+    lines.extend((0..page).map(|i| match rows.get(scroll.1 + i) {
+        Some(row) => row.chars().skip(scroll.0).collect(),
+        None => String::new(),
+    }));
+    lines.push(String::new());
+    lines.push(String::from(HELP));
+    let mut out = io::stdout();
+    for (y, line) in lines.iter().take(height).enumerate() {
+        let line = line.chars().take(width).collect::<String>();
+        queue!(
+            out,
+            cursor::MoveTo(0, y as u16),
+            Print(line),
+            terminal::Clear(ClearType::UntilNewLine)
+        )?;
+    }
+    out.flush()?;
+    Ok(page)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Settings;
+
+    #[test]
+    fn player() {
+        let content = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+        let replay = crate::replay(14, 1, content, &Settings::default())
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let mut player = Player::new(replay);
+        player.jump(5);
+        let fifth = player.draw();
+        assert_eq!(player.status(), "Sand at rest: 5");
+        player.forward();
+        player.forward();
+        player.back();
+        player.back();
+        assert_eq!((player.tick(), player.draw()), (5, fifth));
+        player.jump(1000);
+        assert_eq!((player.tick(), player.end()), (24, Some(24)));
+        assert!(!player.forward());
+
+        let examples = [
+            (9, include_str!("../../day-09/example.txt")),
+            (10, include_str!("../../day-10/example.txt")),
+            (17, include_str!("../../day-17/example.txt")),
+            (23, include_str!("../../day-23/example.txt")),
+            (24, include_str!("../../day-24/example.txt")),
+        ];
+        let settings = Settings::parse("rocks1 = 50\nrocks2 = 50\n").unwrap();
+        for (day, content) in examples {
+            let settings = if day == 17 {
+                &settings
+            } else {
+                &Settings::default()
+            };
+            for part in [1, 2] {
+                let replay = crate::replay(day, part, content, settings);
+                let mut player = Player::new(replay.unwrap().unwrap().unwrap());
+                player.jump(1000);
+                assert!(player.end().is_some(), "day {} part {}", day, part);
+                assert!(!player.draw().is_empty() && !player.status().is_empty());
+            }
+        }
+
+        assert!(crate::replay(1, 1, "1\n", &Settings::default())
+            .unwrap()
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,383 @@
+//! Local HTTP endpoint of `aoc serve`: `POST /day/{n}/part/{p}` with the input
+//! as body solves the part, answering its outcome as JSON like `run all
+//! --format json`. Query parameters set the parameters of the day, like
+//! `?minutes1=20`.
+//!
+//! A fixed pool of threads reads the requests and answers them. Each request
+//! is solved in its own thread with the budget of the options, so the
+//! searches of days 16, 19 and 24 stop with their best answer on timeout.
+//! The other days cannot be stopped: past the timeout and a grace delay, the
+//! request is answered with a 503 and the part is left to finish. A part left
+//! to finish still counts in the solves running at once, past their limit the
+//! requests are answered with a 503 right away.
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use common::{budget::Budget, Settings};
+use log::{info, warn};
+
+use crate::{Outcome, Status};
+
+// Time to send a request, so a slow client cannot keep a thread
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+// Time left to the parts after their timeout, to parse and stop
+const GRACE: Duration = Duration::from_secs(1);
+const ENDPOINT: &str = "POST /day/{n}/part/{p}";
+
+// Parts being solved, answered or not
+static SOLVING: AtomicUsize = AtomicUsize::new(0);
+
+/// Limits of the requests of `aoc serve`.
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// Budget of each part, without timeout a request may run forever
+    pub budget: Budget,
+    /// Largest input, in bytes
+    pub max_body: usize,
+    /// Most parts solved at once, those left to finish after a 503 included
+    pub max_solves: usize,
+    /// Most connections handled at once, the others wait to be accepted
+    pub max_connections: usize,
+}
+
+/// Status code and JSON body of an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Response {
+        let body = json::object! { error: message }.dump();
+        Response { status, body }
+    }
+
+    fn to_http(&self) -> String {
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            411 => "Length Required",
+            413 => "Content Too Large",
+            422 => "Unprocessable Content",
+            500 => "Internal Server Error",
+            _ => "Service Unavailable",
+        };
+        format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\n\
+            Content-Length: {}\r\nConnection: close\r\n\r\n{}\n",
+            self.status,
+            reason,
+            self.body.len() + 1,
+            self.body
+        )
+    }
+}
+
+/// Answers the requests of `listener` until the process ends, with
+/// `max_connections` threads each handling one connection at a time.
+pub fn serve(listener: &TcpListener, options: Options) {
+    thread::scope(|scope| {
+        for _ in 0..options.max_connections {
+            scope.spawn(|| {
+                for stream in listener.incoming() {
+                    match stream {
+                        Ok(stream) => {
+                            if let Err(e) = handle(stream, &options) {
+                                warn!("Request dropped: {}", e);
+                            }
+                        }
+                        Err(e) => warn!("Connection failed: {}", e),
+                    }
+                }
+            });
+        }
+    });
+}
+
+fn handle(mut stream: TcpStream, options: &Options) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let start = Instant::now();
+    let response = match read_request(&mut stream, options.max_body)? {
+        Ok((method, target, body)) => {
+            let response = respond(&method, &target, body, options);
+            info!(
+                "{} {} {} in {:.1?}",
+                method,
+                target,
+                response.status,
+                start.elapsed()
+            );
+            response
+        }
+        Err(response) => response,
+    };
+    stream.write_all(response.to_http().as_bytes())
+}
+
+// Method, target and body of a request, or the response to a bad request
+type Request = (String, String, Vec<u8>);
+
+fn read_request(stream: &mut TcpStream, max_body: usize) -> io::Result<Result<Request, Response>> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let (method, target) = match line.split_whitespace().collect::<Vec<_>>()[..] {
+        [method, target, version] if version.starts_with("HTTP/1.") => {
+            (method.to_string(), target.to_string())
+        }
+        _ => return Ok(Err(Response::error(400, "expecting an HTTP/1.1 request"))),
+    };
+    let (mut length, mut expect_continue) = (None, false);
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Ok(Err(Response::error(400, "expecting `name: value` headers")));
+        };
+        let (name, value) = (name.trim().to_ascii_lowercase(), value.trim());
+        match name.as_str() {
+            "content-length" => match value.parse::<usize>() {
+                Ok(value) => length = Some(value),
+                Err(_) => return Ok(Err(Response::error(400, "bad Content-Length"))),
+            },
+            "expect" => expect_continue = value.eq_ignore_ascii_case("100-continue"),
+            _ => (),
+        }
+    }
+    let length = match (method.as_str(), length) {
+        (_, Some(length)) => length,
+        ("POST", None) => {
+            return Ok(Err(Response::error(
+                411,
+                "the input needs a Content-Length",
+            )))
+        }
+        (_, None) => 0,
+    };
+    if length > max_body {
+        let message = format!("input over {} bytes", max_body);
+        return Ok(Err(Response::error(413, &message)));
+    }
+    if expect_continue {
+        stream.write_all(b"HTTP/1.1 100 Continue\r\n\r\n")?;
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(Ok((method, target, body)))
+}
+
+/// Answers a request: its method, target with the query, and body.
+pub fn respond(method: &str, target: &str, body: Vec<u8>, options: &Options) -> Response {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let (day, part) = match path.split('/').collect::<Vec<_>>()[..] {
+        ["", "day", day, "part", part] => (day, part),
+        _ => return Response::error(404, &format!("no such endpoint, expecting {}", ENDPOINT)),
+    };
+    if method != "POST" {
+        return Response::error(405, &format!("expecting {}", ENDPOINT));
+    }
+    let day = match day.parse::<u32>() {
+        Ok(day) if (1..=25).contains(&day) => day,
+        _ => return Response::error(404, &format!("no day {}", day)),
+    };
+    let part = match part {
+        "1" => 1,
+        "2" => 2,
+        _ => return Response::error(404, &format!("no part {}", part)),
+    };
+    let mut pairs = vec![];
+    for param in query.split('&').filter(|p| !p.is_empty()) {
+        let Some((name, value)) = param.split_once('=') else {
+            let message = format!("query parameters must be NAME=VALUE, not {}", param);
+            return Response::error(400, &message);
+        };
+        let (Some(name), Some(value)) = (decode(name), decode(value)) else {
+            let message = format!("bad percent-encoding in {}", param);
+            return Response::error(400, &message);
+        };
+        pairs.push((name, value));
+    }
+    let settings = match Settings::from_pairs(pairs) {
+        Ok(settings) => settings,
+        Err(e) => return Response::error(400, &e),
+    };
+    let content = match String::from_utf8(body) {
+        Ok(content) => content,
+        Err(_) => return Response::error(400, "the input is not UTF-8"),
+    };
+    let Some(slot) = Slot::take(options.max_solves) else {
+        return Response::error(503, "too many parts being solved, try again later");
+    };
+    match solve(day, part, content, settings, options.budget, slot) {
+        Some(outcome) => {
+            let status = match outcome.status {
+                Status::Solved(_) | Status::Unproven(..) => 200,
+                Status::Error(_) => 422,
+                Status::Panicked(_) => 500,
+            };
+            let body = outcome.to_json();
+            Response { status, body }
+        }
+        None => Response::error(503, "time limit exceeded, the part cannot be stopped"),
+    }
+}
+
+// Decoded query parameter, with `+` for spaces and `%XX` for bytes, None when
+// an escape is not 2 hexadecimal digits or the bytes are not UTF-8
+fn decode(param: &str) -> Option<String> {
+    let mut bytes = vec![];
+    let mut rest = param.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                // from_str_radix alone would take a sign, like `%+1`
+                let hex = rest
+                    .get(..2)
+                    .filter(|h| h.iter().all(u8::is_ascii_hexdigit))?;
+                bytes.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+                rest = &rest[2..];
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+// A part counted in SOLVING until dropped, by the thread solving it
+struct Slot;
+
+impl Slot {
+    fn take(max: usize) -> Option<Slot> {
+        SOLVING
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                (n < max).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| Slot)
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        SOLVING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// The outcome of the part, None if it runs past its timeout and the grace delay
+fn solve(
+    day: u32,
+    part: u8,
+    content: String,
+    settings: Settings,
+    budget: Budget,
+    slot: Slot,
+) -> Option<Outcome> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let read = |_| Ok((content.clone(), settings.clone()));
+        let mut outcomes = crate::run_all(&[day], &[part], budget, read);
+        drop(slot);
+        // The request may have been answered already
+        let _ = sender.send(outcomes.remove(0));
+    });
+    match budget.timeout {
+        Some(timeout) => receiver.recv_timeout(timeout + GRACE).ok(),
+        None => receiver.recv().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests() {
+        let options = Options {
+            budget: Budget {
+                timeout: Some(Duration::from_secs(60)),
+                max_memory: None,
+            },
+            max_body: 100,
+            max_solves: 4,
+            max_connections: 2,
+        };
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(&listener, options));
+        let request = |request: &str| {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            let (head, body) = response.split_once("\r\n\r\n").unwrap();
+            let status = head.split_whitespace().nth(1).unwrap().to_string();
+            (status, json::parse(body).unwrap())
+        };
+
+        let (status, js) =
+            request("POST /day/1/part/2 HTTP/1.1\r\nContent-Length: 7\r\n\r\n1\n2\n\n4\n");
+        assert_eq!((status.as_str(), js["answer"].as_str()), ("200", Some("7")));
+        assert_eq!(js["status"], "ok");
+        let (status, js) = request("POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 2\r\n\r\nx\n");
+        assert_eq!(
+            (status.as_str(), js["status"].as_str()),
+            ("422", Some("error"))
+        );
+        let (status, _) = request("GET /day/1/part/1 HTTP/1.1\r\n\r\n");
+        assert_eq!(status, "405");
+        let (status, _) = request("POST /day/26/part/1 HTTP/1.1\r\nContent-Length: 0\r\n\r\n");
+        assert_eq!(status, "404");
+        let (status, _) = request("POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 101\r\n\r\n");
+        assert_eq!(status, "413");
+
+        let content = include_str!("../../day-16/example.txt");
+        let target = "/day/16/part/1?minutes1=0";
+        let response = respond("POST", target, content.into(), &options);
+        assert_eq!(response.status, 422);
+        let response = respond("POST", "/day/16/part/1?start", vec![], &options);
+        assert_eq!(response.status, 400);
+        let target = "/day/16/part/1?start=%41%41&minutes1=3%30";
+        let response = respond("POST", target, content.into(), &options);
+        assert_eq!(json::parse(&response.body).unwrap()["answer"], "1651");
+        let response = respond("POST", "/day/16/part/1?start=%4", vec![], &options);
+        assert_eq!(response.status, 400);
+        let response = respond("POST", "/day/16/part/1?start=%+1", vec![], &options);
+        assert_eq!(response.status, 400);
+        let response = respond("POST", "/day/16/part/1?=AA", vec![], &options);
+        assert_eq!(response.status, 400);
+        let busy = Options {
+            max_solves: 0,
+            ..options
+        };
+        let response = respond("POST", "/day/1/part/1", vec![], &busy);
+        assert_eq!(response.status, 503);
+        let options = Options {
+            budget: Budget {
+                timeout: Some(Duration::ZERO),
+                max_memory: None,
+            },
+            ..options
+        };
+        let response = respond("POST", "/day/16/part/2", content.into(), &options);
+        let js = json::parse(&response.body).unwrap();
+        assert_eq!(
+            (response.status, js["status"].as_str()),
+            (200, Some("unproven"))
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! Time and memory budgets of the long searches ( days 16, 19 and 24 ), set
+//! by the `aoc` runner ( `--timeout`, `--max-memory` ). A search past its
+//! budget stops with the best answer found so far, reported as unproven.
+//!
+//! Budgets are kept per thread, like the counters, the timeout applies to each
+//! part from its `start`. The memory is what the whole process has allocated,
+//! counted once `CountingAllocator` is the global allocator. An `interrupt`,
+//! from Ctrl-C, stops the searches of every thread.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Budget {
+    /// Time of each part
+    pub timeout: Option<Duration>,
+    /// Bytes allocated by the process
+    pub max_memory: Option<usize>,
+}
+
+/// Why a search stopped before proving its answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Abort {
+    Timeout,
+    Memory,
+    Interrupted,
+}
+
+impl fmt::Display for Abort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Abort::Timeout => write!(f, "timeout"),
+            Abort::Memory => write!(f, "out of memory"),
+            Abort::Interrupted => write!(f, "interrupted"),
+        }
+    }
+}
+
+thread_local! {
+    static BUDGET: Cell<Budget> = const { Cell::new(Budget { timeout: None, max_memory: None }) };
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+    static ABORTED: Cell<Option<Abort>> = const { Cell::new(None) };
+}
+
+/// Sets the budget of the next parts solved by this thread, dropping the
+/// deadline of the last one.
+pub fn set(budget: Budget) {
+    BUDGET.set(budget);
+    DEADLINE.set(None);
+}
+
+/// Starts the clock of a part.
+pub fn start() {
+    DEADLINE.set(BUDGET.get().timeout.map(|timeout| Instant::now() + timeout));
+    ABORTED.set(None);
+}
+
+/// Why the part must stop, if past its budget. Once exceeded, the budget stays
+/// exceeded until the next `start`.
+pub fn exceeded() -> Option<Abort> {
+    if ABORTED.get().is_none() {
+        let budget = BUDGET.get();
+        if INTERRUPTED.load(Ordering::Relaxed) {
+            ABORTED.set(Some(Abort::Interrupted));
+        } else if DEADLINE
+            .get()
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            ABORTED.set(Some(Abort::Timeout));
+        } else if budget.max_memory.is_some_and(|max| allocated() > max) {
+            ABORTED.set(Some(Abort::Memory));
+        }
+    }
+    ABORTED.get()
+}
+
+/// Why the part stopped before proving its answer, None if it did not, and
+/// clears it.
+pub fn take() -> Option<Abort> {
+    ABORTED.take()
+}
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Stops the searches of all the threads, returns whether they already were.
+/// Only sets an atomic, safe in a signal handler.
+pub fn interrupt() -> bool {
+    INTERRUPTED.swap(true, Ordering::Relaxed)
+}
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// Bytes currently allocated by the process, 0 without `CountingAllocator`.
+pub fn allocated() -> usize {
+    ALLOCATED.load(Ordering::Relaxed)
+}
+
+/// The system allocator, counting the allocated bytes for the memory budget:
+/// ```
+/// #[global_allocator]
+/// static ALLOCATOR: common::budget::CountingAllocator = common::budget::CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        }
+        ptr
+    }
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        }
+        ptr
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATED.fetch_add(new_size, Ordering::Relaxed);
+            ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget() {
+        start();
+        assert_eq!(exceeded(), None);
+        set(Budget {
+            timeout: Some(Duration::ZERO),
+            max_memory: None,
+        });
+        assert_eq!(exceeded(), None);
+        start();
+        assert_eq!(exceeded(), Some(Abort::Timeout));
+        assert_eq!(take(), Some(Abort::Timeout));
+        assert_eq!(take(), None);
+        set(Budget::default());
+        start();
+        assert_eq!(exceeded(), None);
+    }
+}
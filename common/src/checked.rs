@@ -0,0 +1,172 @@
+//! Arithmetic at risk of overflow in the days ( 7, 11, 21 and 25 ), checked
+//! when the `aoc` runner is in checked mode ( `--checked` ).
+//!
+//! Unchecked, the operations are the plain ones: they panic on overflow in
+//! debug builds and wrap in release builds. Checked, the first overflow stops
+//! the part with an `Overflow` telling the operation and where it happened,
+//! caught by `catch`. Like the budgets, the mode is kept per thread.
+use std::{
+    any::type_name,
+    cell::Cell,
+    fmt,
+    ops::{Add, Div, Mul, Sub},
+    panic::{self, AssertUnwindSafe},
+};
+
+/// Integers of the checked operations.
+pub trait Int:
+    Copy
+    + fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+    fn pow(self, exp: u32) -> Self;
+}
+
+macro_rules! int {
+    ($($t:ty),*) => {
+        $(impl Int for $t {
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+            fn checked_div(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_div(self, rhs)
+            }
+            fn checked_pow(self, exp: u32) -> Option<Self> {
+                <$t>::checked_pow(self, exp)
+            }
+            fn pow(self, exp: u32) -> Self {
+                <$t>::pow(self, exp)
+            }
+        })*
+    };
+}
+
+int!(u32, u64, i64, usize);
+
+/// An operation out of the range of its type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    /// The operation with its operands, like `3 - 5`
+    pub operation: String,
+    pub type_name: &'static str,
+    /// Where it happened, like `monkey root`
+    pub context: String,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` out of {}, in {}",
+            self.operation, self.type_name, self.context
+        )
+    }
+}
+
+thread_local! {
+    static CHECKED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Checks the operations of the next parts solved by this thread.
+pub fn set(checked: bool) {
+    CHECKED.set(checked);
+}
+
+pub fn enabled() -> bool {
+    CHECKED.get()
+}
+
+/// Runs `f`, stopped by its first overflow in checked mode.
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, Overflow> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => Ok(value),
+        Err(payload) => match payload.downcast::<Overflow>() {
+            Ok(overflow) => Err(*overflow),
+            Err(payload) => panic::resume_unwind(payload),
+        },
+    }
+}
+
+// Unwinds without the panic hook, the overflow is not a bug of the day
+fn check<T: Int>(
+    checked: impl FnOnce() -> Option<T>,
+    plain: impl FnOnce() -> T,
+    operation: impl FnOnce() -> String,
+    context: impl FnOnce() -> String,
+) -> T {
+    if !enabled() {
+        return plain();
+    }
+    match checked() {
+        Some(value) => value,
+        None => panic::resume_unwind(Box::new(Overflow {
+            operation: operation(),
+            type_name: type_name::<T>(),
+            context: context(),
+        })),
+    }
+}
+
+/// `a + b`, `context` telling where in checked mode.
+pub fn add<T: Int>(a: T, b: T, context: impl FnOnce() -> String) -> T {
+    let operation = || format!("{} + {}", a, b);
+    check(|| a.checked_add(b), || a + b, operation, context)
+}
+
+/// `a - b`, `context` telling where in checked mode.
+pub fn sub<T: Int>(a: T, b: T, context: impl FnOnce() -> String) -> T {
+    let operation = || format!("{} - {}", a, b);
+    check(|| a.checked_sub(b), || a - b, operation, context)
+}
+
+/// `a * b`, `context` telling where in checked mode.
+pub fn mul<T: Int>(a: T, b: T, context: impl FnOnce() -> String) -> T {
+    let operation = || format!("{} * {}", a, b);
+    check(|| a.checked_mul(b), || a * b, operation, context)
+}
+
+/// `a / b`, a division by zero is reported too in checked mode.
+pub fn div<T: Int>(a: T, b: T, context: impl FnOnce() -> String) -> T {
+    let operation = || format!("{} / {}", a, b);
+    check(|| a.checked_div(b), || a / b, operation, context)
+}
+
+/// `a ^ exp`, `context` telling where in checked mode.
+pub fn pow<T: Int>(a: T, exp: u32, context: impl FnOnce() -> String) -> T {
+    let operation = || format!("{} ^ {}", a, exp);
+    check(|| a.checked_pow(exp), || a.pow(exp), operation, context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflows() {
+        let context = || String::from("test");
+        assert_eq!(catch(|| sub(5u32, 3, context)), Ok(2));
+        set(true);
+        assert_eq!(catch(|| mul(1u64 << 32, 1 << 31, context)), Ok(1 << 63));
+        let overflow = catch(|| add(1, sub(3u64, 5, context), context)).unwrap_err();
+        assert_eq!(overflow.to_string(), "`3 - 5` out of u64, in test");
+        assert!(catch(|| div(1i64, 0, context)).is_err());
+        assert_eq!(catch(|| pow(5i64, 27, context)), Ok(5i64.pow(27)));
+        assert!(catch(|| pow(5i64, 28, context)).is_err());
+        set(false);
+        assert!(!enabled());
+    }
+}
@@ -0,0 +1,332 @@
+//! Checkpoints of the long searches ( days 16 and 19 ), saved to a file by the
+//! `aoc` runner ( `--checkpoint FILE` ) to resume them once the process is
+//! stopped or killed ( `--resume` ).
+//!
+//! A part may run several searches, numbered in the order they begin. Each one
+//! saves its state every `every` and when it stops early ( budget or Ctrl-C ),
+//! and its best score once done. A resumed part runs the same searches in the
+//! same order: the done ones return their saved best, the others continue from
+//! their saved state.
+//!
+//! The file is text: a header line with the tag of the input, then a line per
+//! search, `part number done|paused` followed by the numbers of its `Record`.
+//! Like the budgets, checkpoints are kept per thread.
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    fs,
+    hash::Hash,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use log::{debug, warn};
+
+/// Values saved in a checkpoint, as a list of numbers.
+pub trait Record: Sized {
+    fn record(&self, numbers: &mut Vec<u64>);
+    /// None if the numbers are not a record of the type.
+    fn restore(numbers: &mut impl Iterator<Item = u64>) -> Option<Self>;
+}
+
+macro_rules! record_unsigned {
+    ($($t:ty),*) => {
+        $(impl Record for $t {
+            fn record(&self, numbers: &mut Vec<u64>) {
+                numbers.push(*self as u64);
+            }
+            fn restore(numbers: &mut impl Iterator<Item = u64>) -> Option<Self> {
+                numbers.next()?.try_into().ok()
+            }
+        })*
+    };
+}
+
+record_unsigned!(u8, u16, u32, u64, usize);
+
+impl Record for () {
+    fn record(&self, _numbers: &mut Vec<u64>) {}
+    fn restore(_numbers: &mut impl Iterator<Item = u64>) -> Option<()> {
+        Some(())
+    }
+}
+
+impl<A: Record, B: Record> Record for (A, B) {
+    fn record(&self, numbers: &mut Vec<u64>) {
+        self.0.record(numbers);
+        self.1.record(numbers);
+    }
+    fn restore(numbers: &mut impl Iterator<Item = u64>) -> Option<Self> {
+        Some((A::restore(numbers)?, B::restore(numbers)?))
+    }
+}
+
+impl<A: Record, B: Record, C: Record> Record for (A, B, C) {
+    fn record(&self, numbers: &mut Vec<u64>) {
+        self.0.record(numbers);
+        self.1.record(numbers);
+        self.2.record(numbers);
+    }
+    fn restore(numbers: &mut impl Iterator<Item = u64>) -> Option<Self> {
+        Some((
+            A::restore(numbers)?,
+            B::restore(numbers)?,
+            C::restore(numbers)?,
+        ))
+    }
+}
+
+impl<T: Record> Record for Option<T> {
+    fn record(&self, numbers: &mut Vec<u64>) {
+        match self {
+            None => numbers.push(0),
+            Some(value) => {
+                numbers.push(1);
+                value.record(numbers);
+            }
+        }
+    }
+    fn restore(numbers: &mut impl Iterator<Item = u64>) -> Option<Self> {
+        match numbers.next()? {
+            0 => Some(None),
+            1 => Some(Some(T::restore(numbers)?)),
+            _ => None,
+        }
+    }
+}
+
+// Length first, then the items
+impl<T: Record> Record for Vec<T> {
+    fn record(&self, numbers: &mut Vec<u64>) {
+        numbers.push(self.len() as u64);
+        for item in self {
+            item.record(numbers);
+        }
+    }
+    fn restore(numbers: &mut impl Iterator<Item = u64>) -> Option<Self> {
+        let len = numbers.next()?;
+        (0..len).map(|_| T::restore(numbers)).collect()
+    }
+}
+
+impl<K: Record + Hash + Eq, V: Record> Record for HashMap<K, V> {
+    fn record(&self, numbers: &mut Vec<u64>) {
+        numbers.push(self.len() as u64);
+        for (key, value) in self {
+            key.record(numbers);
+            value.record(numbers);
+        }
+    }
+    fn restore(numbers: &mut impl Iterator<Item = u64>) -> Option<Self> {
+        let len = numbers.next()?;
+        (0..len)
+            .map(|_| Some((K::restore(numbers)?, V::restore(numbers)?)))
+            .collect()
+    }
+}
+
+/// What a checkpoint has of a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Saved {
+    /// Record of its result
+    Done(Vec<u64>),
+    /// Record of its state, to continue it
+    Paused(Vec<u64>),
+}
+
+struct Checkpoint {
+    file: PathBuf,
+    tag: String,
+    every: Duration,
+    searches: BTreeMap<(u8, usize), Saved>,
+    part: u8,
+    // Number of the next search of the part
+    next: usize,
+    saved: Instant,
+}
+
+impl Checkpoint {
+    fn header(&self) -> String {
+        format!("aoc checkpoint {}", self.tag)
+    }
+    fn read(&mut self) -> Result<(), String> {
+        let file = self.file.display();
+        let content = fs::read_to_string(&self.file)
+            .map_err(|e| format!("Cannot read the checkpoint '{}': {}", file, e))?;
+        let mut lines = content.lines();
+        if lines.next() != Some(self.header().as_str()) {
+            return Err(format!(
+                "'{}' is the checkpoint of another input or parameters",
+                file
+            ));
+        }
+        for (i, line) in lines.enumerate() {
+            let invalid = || format!("'{}' line {}: invalid checkpoint", file, i + 2);
+            let mut words = line.split_whitespace();
+            let part = words.next().and_then(|w| w.parse().ok());
+            let number = words.next().and_then(|w| w.parse().ok());
+            let kind = words.next();
+            let numbers = words
+                .map(|w| w.parse::<u64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid())?;
+            let saved = match kind {
+                Some("done") => Saved::Done(numbers),
+                Some("paused") => Saved::Paused(numbers),
+                _ => return Err(invalid()),
+            };
+            let (Some(part), Some(number)) = (part, number) else {
+                return Err(invalid());
+            };
+            self.searches.insert((part, number), saved);
+        }
+        Ok(())
+    }
+    // Written next to the file first, a kill while writing keeps the last one
+    fn write(&self) -> io::Result<()> {
+        // Appended to the name, `a.json` and `a.bin` would share `a.tmp` otherwise
+        let mut temporary = self.file.clone().into_os_string();
+        temporary.push(".tmp");
+        let mut out = BufWriter::new(fs::File::create(&temporary)?);
+        writeln!(out, "{}", self.header())?;
+        for (&(part, number), saved) in self.searches.iter() {
+            let (kind, numbers) = match saved {
+                Saved::Done(numbers) => ("done", numbers),
+                Saved::Paused(numbers) => ("paused", numbers),
+            };
+            write!(out, "{} {} {}", part, number, kind)?;
+            for n in numbers {
+                write!(out, " {}", n)?;
+            }
+            writeln!(out)?;
+        }
+        out.into_inner()?.sync_all()?;
+        fs::rename(&temporary, &self.file)
+    }
+}
+
+thread_local! {
+    static CHECKPOINT: RefCell<Option<Checkpoint>> = const { RefCell::new(None) };
+}
+
+/// Saves the searches of the next parts solved by this thread to `file`, every
+/// `every`, continuing those already in the file if `resume`. The `tag` tells
+/// the input and parameters apart, the checkpoint of another one cannot be
+/// resumed.
+pub fn set(file: &Path, tag: &str, every: Duration, resume: bool) -> Result<(), String> {
+    let mut checkpoint = Checkpoint {
+        file: file.to_path_buf(),
+        tag: tag.to_string(),
+        every,
+        searches: BTreeMap::new(),
+        part: 0,
+        next: 0,
+        saved: Instant::now(),
+    };
+    if resume {
+        checkpoint.read()?;
+    }
+    CHECKPOINT.set(Some(checkpoint));
+    Ok(())
+}
+
+/// Stops saving the searches of this thread.
+pub fn clear() {
+    CHECKPOINT.set(None);
+}
+
+/// Starts the searches of a part.
+pub fn start(part: u8) {
+    CHECKPOINT.with_borrow_mut(|checkpoint| {
+        if let Some(checkpoint) = checkpoint {
+            checkpoint.part = part;
+            checkpoint.next = 0;
+        }
+    });
+}
+
+/// Number of the search beginning, with what the checkpoint has of it. None
+/// without checkpoint.
+pub fn begin() -> Option<(usize, Option<Saved>)> {
+    CHECKPOINT.with_borrow_mut(|checkpoint| {
+        let checkpoint = checkpoint.as_mut()?;
+        let number = checkpoint.next;
+        checkpoint.next += 1;
+        let saved = checkpoint.searches.get(&(checkpoint.part, number)).cloned();
+        Some((number, saved))
+    })
+}
+
+/// Whether the search should save its state, `every` after the last save.
+pub fn due() -> bool {
+    CHECKPOINT.with_borrow(|checkpoint| {
+        checkpoint
+            .as_ref()
+            .is_some_and(|checkpoint| checkpoint.saved.elapsed() >= checkpoint.every)
+    })
+}
+
+/// Saves a search of the part and writes the file. A failure to write is
+/// logged, the search goes on.
+pub fn save(number: usize, saved: Saved) {
+    CHECKPOINT.with_borrow_mut(|checkpoint| {
+        let Some(checkpoint) = checkpoint else {
+            return;
+        };
+        checkpoint.searches.insert((checkpoint.part, number), saved);
+        match checkpoint.write() {
+            Ok(()) => debug!("Search {} saved to {}", number, checkpoint.file.display()),
+            Err(e) => warn!("Cannot save to {}: {}", checkpoint.file.display(), e),
+        }
+        checkpoint.saved = Instant::now();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn round_trip<T: Record + PartialEq + std::fmt::Debug>(value: T) {
+        let mut numbers = vec![];
+        value.record(&mut numbers);
+        let mut numbers = numbers.into_iter();
+        assert_eq!(T::restore(&mut numbers), Some(value));
+        assert_eq!(numbers.next(), None);
+    }
+
+    #[test]
+    fn records() {
+        round_trip((3u8, 70_000u32, u64::MAX));
+        round_trip(vec![Some((1usize, ())), None]);
+        round_trip(HashMap::from([(vec![1u32, 2], 3u32), (vec![], 4)]));
+        assert_eq!(u8::restore(&mut [256].into_iter()), None);
+        assert_eq!(Vec::<u32>::restore(&mut [2, 1].into_iter()), None);
+    }
+
+    #[test]
+    fn checkpoint() {
+        let file = env::temp_dir().join(format!("aoc-checkpoint-{}.txt", std::process::id()));
+        assert_eq!(begin(), None);
+        set(&file, "day-00", Duration::ZERO, false).unwrap();
+        start(1);
+        assert_eq!(begin(), Some((0, None)));
+        assert!(due());
+        save(0, Saved::Done(vec![7]));
+        assert_eq!(begin(), Some((1, None)));
+        save(1, Saved::Paused(vec![]));
+
+        set(&file, "day-00", Duration::from_secs(60), true).unwrap();
+        assert!(!due());
+        start(1);
+        assert_eq!(begin(), Some((0, Some(Saved::Done(vec![7])))));
+        assert_eq!(begin(), Some((1, Some(Saved::Paused(vec![])))));
+        start(2);
+        assert_eq!(begin(), Some((0, None)));
+        let other = set(&file, "day-01", Duration::ZERO, true);
+        assert!(other.unwrap_err().contains("another input"));
+        clear();
+        fs::remove_file(&file).unwrap();
+    }
+}
@@ -0,0 +1,54 @@
+//! Internal counters of a solver ( iterations, cache sizes, ... ), reported
+//! along with the answer by the `aoc` runner.
+//!
+//! Counters are kept per thread, `take` returns those set since the last call.
+use std::cell::RefCell;
+
+thread_local! {
+    static COUNTERS: RefCell<Vec<(String, u64)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Set the counter `name` to `value`.
+pub fn set(name: &str, value: u64) {
+    COUNTERS.with(|counters| {
+        let mut counters = counters.borrow_mut();
+        match counters.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => *v = value,
+            None => counters.push((name.to_string(), value)),
+        }
+    });
+}
+
+/// Add `value` to the counter `name`, starting from 0.
+pub fn add(name: &str, value: u64) {
+    COUNTERS.with(|counters| {
+        let mut counters = counters.borrow_mut();
+        match counters.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => *v += value,
+            None => counters.push((name.to_string(), value)),
+        }
+    });
+}
+
+/// Counters set since the last call, in the order they were first set.
+pub fn take() -> Vec<(String, u64)> {
+    COUNTERS.with(|counters| counters.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_add_take() {
+        set("iterations", 3);
+        add("count", 2);
+        add("count", 5);
+        set("iterations", 4);
+        assert_eq!(
+            take(),
+            vec![(String::from("iterations"), 4), (String::from("count"), 7)]
+        );
+        assert!(take().is_empty());
+    }
+}
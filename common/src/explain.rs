@@ -0,0 +1,62 @@
+//! Derivation of the answers, narrated by the days in explain mode
+//! ( `--explain` of the `aoc` runner ): the steps that lead to an answer, like
+//! the score of each round or the directory chosen to be deleted.
+//!
+//! Like the checked mode, the mode is kept per thread. The steps are written
+//! with `explain!`, only formatted in explain mode, and `take` returns those
+//! written since the last call.
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static EXPLAIN: Cell<bool> = const { Cell::new(false) };
+    static STEPS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Explains the next parts solved by this thread.
+pub fn set(explain: bool) {
+    EXPLAIN.set(explain);
+}
+
+pub fn enabled() -> bool {
+    EXPLAIN.get()
+}
+
+/// Adds a step to the explanation, see `explain!`.
+pub fn step(step: String) {
+    STEPS.with(|steps| steps.borrow_mut().push(step));
+}
+
+/// Steps written since the last call.
+pub fn take() -> Vec<String> {
+    STEPS.with(|steps| steps.take())
+}
+
+/// Writes a step of the explanation in explain mode, with the arguments of
+/// `format!`:
+/// ```
+/// common::explain!("Round {}: {} points", 1, 8);
+/// ```
+#[macro_export]
+macro_rules! explain {
+    ($($arg:tt)*) => {
+        if $crate::explain::enabled() {
+            $crate::explain::step(format!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps() {
+        explain!("not explained");
+        assert!(take().is_empty());
+        set(true);
+        explain!("Round {}: {} points", 1, 8);
+        set(false);
+        assert_eq!(take(), ["Round 1: 8 points"]);
+        assert!(take().is_empty());
+    }
+}
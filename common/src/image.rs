@@ -0,0 +1,149 @@
+use std::fmt::Write;
+
+/// Pixels of an image, row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pixels {
+    /// Black ( true ) or white
+    Bits(Vec<bool>),
+    /// Gray levels from 0 ( black ) to the maximum ( white )
+    Grays(u16, Vec<u16>),
+    Colors(Vec<[u8; 3]>),
+}
+
+/// An image written in the plain ( ASCII ) Netpbm formats: PBM for black and
+/// white, PGM for grays and PPM for colors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Pixels,
+}
+
+// Lines of the plain formats are at most 70 characters long
+const LINE_LENGTH: usize = 70;
+
+impl Image {
+    pub fn bitmap(width: usize, height: usize, pixels: Vec<bool>) -> Image {
+        assert_eq!(pixels.len(), width * height, "bitmap size");
+        Image {
+            width,
+            height,
+            pixels: Pixels::Bits(pixels),
+        }
+    }
+    pub fn graymap(width: usize, height: usize, max: u16, pixels: Vec<u16>) -> Image {
+        assert_eq!(pixels.len(), width * height, "graymap size");
+        assert!(max > 0 && pixels.iter().all(|&p| p <= max), "gray levels");
+        Image {
+            width,
+            height,
+            pixels: Pixels::Grays(max, pixels),
+        }
+    }
+    pub fn pixmap(width: usize, height: usize, pixels: Vec<[u8; 3]>) -> Image {
+        assert_eq!(pixels.len(), width * height, "pixmap size");
+        Image {
+            width,
+            height,
+            pixels: Pixels::Colors(pixels),
+        }
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// File extension of the format: `pbm`, `pgm` or `ppm`.
+    pub fn extension(&self) -> &'static str {
+        match self.pixels {
+            Pixels::Bits(_) => "pbm",
+            Pixels::Grays(_, _) => "pgm",
+            Pixels::Colors(_) => "ppm",
+        }
+    }
+    /// Every pixel becomes a square of `factor` pixels wide.
+    pub fn scale(&self, factor: usize) -> Image {
+        fn scale<T: Copy>(pixels: &[T], width: usize, factor: usize) -> Vec<T> {
+            let mut scaled = Vec::with_capacity(pixels.len() * factor * factor);
+            for row in pixels.chunks(width.max(1)) {
+                let row = row
+                    .iter()
+                    .flat_map(|&p| std::iter::repeat_n(p, factor))
+                    .collect::<Vec<_>>();
+                for _ in 0..factor {
+                    scaled.extend_from_slice(&row);
+                }
+            }
+            scaled
+        }
+        let (width, height) = (self.width * factor, self.height * factor);
+        let pixels = match &self.pixels {
+            Pixels::Bits(bits) => Pixels::Bits(scale(bits, self.width, factor)),
+            Pixels::Grays(max, grays) => Pixels::Grays(*max, scale(grays, self.width, factor)),
+            Pixels::Colors(colors) => Pixels::Colors(scale(colors, self.width, factor)),
+        };
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+    /// The image in its plain Netpbm format.
+    pub fn to_netpbm(&self) -> String {
+        let (magic, max, values) = match &self.pixels {
+            Pixels::Bits(bits) => ("P1", None, bits.iter().map(|&b| b as u16).collect()),
+            Pixels::Grays(max, grays) => ("P2", Some(*max), grays.clone()),
+            Pixels::Colors(colors) => (
+                "P3",
+                Some(255),
+                colors.iter().flatten().map(|&c| c as u16).collect(),
+            ),
+        };
+        let mut s = format!("{}\n{} {}\n", magic, self.width, self.height);
+        if let Some(max) = max {
+            writeln!(s, "{}", max).unwrap();
+        }
+        let mut line = String::new();
+        for value in values {
+            let value = value.to_string();
+            if line.len() + 1 + value.len() > LINE_LENGTH {
+                s += &line;
+                s.push('\n');
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line += &value;
+        }
+        if !line.is_empty() {
+            s += &line;
+            s.push('\n');
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn netpbm() {
+        let bitmap = Image::bitmap(3, 2, vec![true, false, true, false, false, true]);
+        assert_eq!(bitmap.to_netpbm(), "P1\n3 2\n1 0 1 0 0 1\n");
+        assert_eq!(bitmap.extension(), "pbm");
+
+        let graymap = Image::graymap(2, 1, 9, vec![0, 9]).scale(2);
+        assert_eq!((graymap.width(), graymap.height()), (4, 2));
+        assert_eq!(graymap.to_netpbm(), "P2\n4 2\n9\n0 0 9 9 0 0 9 9\n");
+
+        let pixmap = Image::pixmap(20, 1, vec![[255, 0, 128]; 20]);
+        let netpbm = pixmap.to_netpbm();
+        assert!(netpbm.starts_with("P3\n20 1\n255\n255 0 128 255 0 128 "));
+        assert!(netpbm.lines().all(|line| line.len() <= LINE_LENGTH));
+        let values = netpbm.lines().skip(3).flat_map(|l| l.split(' ')).count();
+        assert_eq!(values, 60);
+    }
+}
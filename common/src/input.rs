@@ -0,0 +1,106 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+/// Where the input of a day is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// `day-XX/input.txt`, the default.
+    Input,
+    /// `day-XX/example.txt`, or `day-XX/exampleN.txt` with a suffix N.
+    Example(String),
+    File(String),
+    /// `-`
+    Stdin,
+}
+
+impl Source {
+    /// `-` is stdin, anything else a file path.
+    pub fn from_arg(arg: &str) -> Source {
+        if arg == "-" {
+            Source::Stdin
+        } else {
+            Source::File(arg.to_string())
+        }
+    }
+    /// Removes `--example [N]` from `args`, N being a number or a letter
+    /// ( `example2.txt`, `exampleA.txt` ).
+    pub fn take_example(args: &mut Vec<String>) -> Option<Source> {
+        let i = args.iter().position(|a| a == "--example")?;
+        args.remove(i);
+        let suffix = match args.get(i) {
+            Some(n) if is_example_suffix(n) => args.remove(i),
+            _ => String::new(),
+        };
+        Some(Source::Example(suffix))
+    }
+    /// Name of the input for the diagnostics, and its content.
+    /// `dir` is the directory of the day, `day-XX`.
+    pub fn read(&self, dir: &str) -> Result<(String, String), String> {
+        let name = match self {
+            Source::Stdin => {
+                let mut content = String::new();
+                return match io::stdin().read_to_string(&mut content) {
+                    Ok(_) => Ok((String::from("<stdin>"), content)),
+                    Err(e) => Err(format!("Cannot read stdin: {}", e)),
+                };
+            }
+            Source::File(file) => file.clone(),
+            Source::Input => day_file(dir, "input.txt"),
+            Source::Example(n) => day_file(dir, &format!("example{}.txt", n)),
+        };
+        match fs::read_to_string(&name) {
+            Ok(content) => Ok((name, content)),
+            Err(e) => Err(format!("Cannot read '{}': {}", name, e)),
+        }
+    }
+}
+
+fn is_example_suffix(arg: &str) -> bool {
+    arg.chars().all(|c| c.is_ascii_digit())
+        || (arg.len() == 1 && arg.chars().all(|c| c.is_ascii_uppercase()))
+}
+
+// `day-XX/name` from the workspace root, or `name` from the day directory
+fn day_file(dir: &str, name: &str) -> String {
+    let path = format!("{}/{}", dir, name);
+    if !Path::new(&path).exists() && Path::new(name).exists() {
+        name.to_string()
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn take_example() {
+        let mut a = args(&["run", "9", "1", "--example", "2"]);
+        assert_eq!(
+            Source::take_example(&mut a),
+            Some(Source::Example(String::from("2")))
+        );
+        assert_eq!(a, args(&["run", "9", "1"]));
+        let mut a = args(&["--example", "-v"]);
+        assert_eq!(
+            Source::take_example(&mut a),
+            Some(Source::Example(String::new()))
+        );
+        assert_eq!(a, args(&["-v"]));
+        let mut a = args(&["2", "--example", "A"]);
+        assert_eq!(
+            Source::take_example(&mut a),
+            Some(Source::Example(String::from("A")))
+        );
+        assert_eq!(Source::take_example(&mut a), None);
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+    }
+}
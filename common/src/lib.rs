@@ -0,0 +1,205 @@
+use std::{cell::Cell, env, fmt::Display, process};
+
+pub mod budget;
+pub mod checked;
+pub mod checkpoint;
+pub mod counters;
+pub mod explain;
+mod image;
+mod input;
+pub mod logger;
+mod params;
+mod parse;
+mod simulation;
+
+pub use image::Image;
+pub use input::Source;
+pub use params::{unknown_param, Error, Params, Settings};
+pub use parse::{lines, sections, ParseError, Token};
+pub use simulation::{Replay, Simulation};
+
+/// A day of the puzzle: the input is parsed once, then each part is solved from it.
+pub trait Solver {
+    /// Shared by the threads solving the parts, see `Parts`.
+    type Input: Send + Sync;
+    /// Parameters of the puzzle, `()` for the days without.
+    type Params: Params + Send + Sync;
+    type Answer1: Display;
+    type Answer2: Display;
+    fn parse(content: &str) -> Result<Self::Input, ParseError>;
+    /// Checks that the parameters fit the input, like a start valve that exists.
+    fn check(_input: &Self::Input, _params: &Self::Params) -> Result<(), String> {
+        Ok(())
+    }
+    /// Checks that the input fits a part, like the cube net of day-22 part 2,
+    /// before solving, replaying or drawing the part.
+    fn check_part(
+        _input: &Self::Input,
+        _params: &Self::Params,
+        _part: u8,
+    ) -> Result<(), ParseError> {
+        Ok(())
+    }
+    fn part1(input: &Self::Input, params: &Self::Params) -> Self::Answer1;
+    fn part2(input: &Self::Input, params: &Self::Params) -> Self::Answer2;
+    /// Simulation of a part, for the days that the terminal player replays.
+    fn simulation(
+        _input: &Self::Input,
+        _params: &Self::Params,
+        _part: u8,
+    ) -> Option<Box<dyn Simulation>> {
+        None
+    }
+    /// Named images of the state of a part, for the map days without
+    /// simulation ( `aoc image` ).
+    fn images(
+        _input: &Self::Input,
+        _params: &Self::Params,
+        _part: u8,
+    ) -> Vec<(&'static str, Image)> {
+        vec![]
+    }
+}
+
+/// Solves any part (1 or 2) of an input parsed once, from any thread, unless
+/// the input does not fit the part ( see `Solver::check_part` ).
+pub type Parts = Box<dyn Fn(u8) -> Result<String, Error> + Send + Sync>;
+
+/// Parse `content` and set the parameters, to then solve its parts without
+/// parsing it again.
+pub fn prepare<S: Solver>(content: &str, settings: &Settings) -> Result<Parts, Error>
+where
+    S::Input: 'static,
+    S::Params: 'static,
+{
+    let params = settings.apply::<S::Params>().map_err(Error::Param)?;
+    let input = S::parse(content)?;
+    S::check(&input, &params).map_err(Error::Param)?;
+    Ok(Box::new(move |part| {
+        S::check_part(&input, &params, part)?;
+        Ok(match part {
+            1 => S::part1(&input, &params).to_string(),
+            2 => S::part2(&input, &params).to_string(),
+            _ => panic!("part number must be 1 or 2, not {}", part),
+        })
+    }))
+}
+
+/// Parse `content` and set the parameters, to then replay the simulation of
+/// a part. None for the days without simulation.
+pub fn replay<S: Solver>(
+    content: &str,
+    settings: &Settings,
+    part: u8,
+) -> Result<Option<Replay>, Error>
+where
+    S::Input: 'static,
+    S::Params: 'static,
+{
+    let params = settings.apply::<S::Params>().map_err(Error::Param)?;
+    let input = S::parse(content)?;
+    S::check(&input, &params).map_err(Error::Param)?;
+    S::check_part(&input, &params, part)?;
+    let Some(first) = S::simulation(&input, &params, part) else {
+        return Ok(None);
+    };
+    // The first replay takes the simulation built here, the next ones rebuild it
+    let first = Cell::new(Some(first));
+    Ok(Some(Box::new(move || {
+        first
+            .take()
+            .unwrap_or_else(|| S::simulation(&input, &params, part).unwrap())
+    })))
+}
+
+/// Parse `content` and set the parameters, to then draw the images of a part.
+pub fn images<S: Solver>(
+    content: &str,
+    settings: &Settings,
+    part: u8,
+) -> Result<Vec<(&'static str, Image)>, Error> {
+    let params = settings.apply::<S::Params>().map_err(Error::Param)?;
+    let input = S::parse(content)?;
+    S::check(&input, &params).map_err(Error::Param)?;
+    S::check_part(&input, &params, part)?;
+    Ok(S::images(&input, &params, part))
+}
+
+/// Parse `content` and solve the requested part (1 or 2).
+pub fn solve<S: Solver>(content: &str, settings: &Settings, part: u8) -> Result<String, Error>
+where
+    S::Input: 'static,
+    S::Params: 'static,
+{
+    prepare::<S>(content, settings).and_then(|parts| parts(part))
+}
+
+/// Parts selected by a `1`, `2` or `both` argument.
+pub fn parse_parts(arg: &str) -> Option<Vec<u8>> {
+    match arg {
+        "1" => Some(vec![1]),
+        "2" => Some(vec![2]),
+        "both" => Some(vec![1, 2]),
+        _ => None,
+    }
+}
+
+const USAGE: &str =
+    "Usage: day-XX [FILE|-|--example [N]] [1|2|both] [--param NAME=VALUE]... [-v|-vv]";
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+/// Shared `main` of the day-XX binaries, see `USAGE`.
+/// `dir` is the directory of the day, whose `input.txt` is read by default.
+/// Without a part number, both parts are solved from a single parse.
+/// The parameters of the input config file are overridden by the `--param` ones.
+pub fn main<S: Solver>(dir: &str)
+where
+    S::Input: 'static,
+    S::Params: 'static,
+{
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    logger::init(logger::verbosity(&mut args));
+    let overrides = Settings::take_args(&mut args).unwrap_or_else(|e| usage_error(&e));
+    let mut source = Source::take_example(&mut args);
+    let mut parts = vec![1, 2];
+    for arg in args {
+        match parse_parts(&arg) {
+            Some(selected) => parts = selected,
+            None if source.is_none() => source = Some(Source::from_arg(&arg)),
+            None => usage_error(&format!("unexpected argument {}", arg)),
+        }
+    }
+    let (filename, content) = match source.unwrap_or(Source::Input).read(dir) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    let settings = match Settings::config(&filename) {
+        Ok(config) => config.merge(&overrides),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    let fail = |e: Error| -> ! {
+        match e {
+            Error::Param(e) => usage_error(&e),
+            Error::Parse(e) => {
+                eprint!("{}", e.in_file(&filename).diagnostic(&content));
+                process::exit(1);
+            }
+        }
+    };
+    let solver = prepare::<S>(&content, &settings).unwrap_or_else(|e| fail(e));
+    for part in parts {
+        let answer = solver(part).unwrap_or_else(|e| fail(e));
+        println!("Part {}: {}", part, answer);
+    }
+}
@@ -0,0 +1,55 @@
+//! Minimal stderr logger behind the `log` macros used by the days.
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+struct Logger;
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let level = match record.level() {
+                Level::Error => "error",
+                Level::Warn => "warning",
+                Level::Info => "info",
+                Level::Debug => "debug",
+                Level::Trace => "trace",
+            };
+            eprintln!("[{} {}] {}", level, record.target(), record.args());
+        }
+    }
+    fn flush(&self) {}
+}
+
+static LOGGER: Logger = Logger;
+
+/// Install the logger. Quiet by default ( warnings only ), each `verbosity`
+/// level enables more: 1 for `info`, 2 for `debug`, 3 for `trace`.
+pub fn init(verbosity: u8) {
+    // Only fails if already installed, the level is still updated
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    });
+}
+
+/// Count and remove the verbosity flags ( `-v`, `-vv`, `-vvv`, `--verbose` ) from `args`.
+pub fn verbosity(args: &mut Vec<String>) -> u8 {
+    let mut verbosity = 0;
+    args.retain(|arg| {
+        if arg == "--verbose" {
+            verbosity += 1;
+            false
+        } else if arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v') {
+            verbosity += arg.len() as u8 - 1;
+            false
+        } else {
+            true
+        }
+    });
+    verbosity
+}
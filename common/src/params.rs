@@ -0,0 +1,250 @@
+use std::{fmt, fs, path::Path};
+
+use crate::ParseError;
+
+/// Named parameters of a day, defaulting to the values of the puzzle statement,
+/// that can be changed to explore variants of the puzzle. See `params!`.
+pub trait Params: Default {
+    /// Name, description and current value of every parameter.
+    fn list(&self) -> Vec<(&'static str, &'static str, String)>;
+    /// Sets a parameter from its text value.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String>;
+}
+
+/// Days without parameters.
+impl Params for () {
+    fn list(&self) -> Vec<(&'static str, &'static str, String)> {
+        vec![]
+    }
+    fn set(&mut self, name: &str, _: &str) -> Result<(), String> {
+        Err(format!("unknown parameter `{}`, this day has none", name))
+    }
+}
+
+/// Error of an unknown parameter name, listing the known ones.
+pub fn unknown_param<P: Params>(params: &P, name: &str) -> String {
+    let known = params
+        .list()
+        .iter()
+        .map(|(name, description, value)| format!("\n  {} = {}  ( {} )", name, value, description))
+        .collect::<String>();
+    format!("unknown parameter `{}`, expected one of:{}", name, known)
+}
+
+/// Defines a parameters struct, with the default value and description of each field:
+/// ```
+/// common::params! {
+///     pub struct Params {
+///         /// Rounds of part 1
+///         pub rounds: u32 = 20,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $(
+                #[doc = $doc:literal]
+                pub $field:ident: $ty:ty = $default:expr,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $name {
+            $(
+                #[doc = $doc]
+                pub $field: $ty,
+            )*
+        }
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    $($field: $default,)*
+                }
+            }
+        }
+        impl $crate::Params for $name {
+            fn list(&self) -> Vec<(&'static str, &'static str, String)> {
+                vec![$((stringify!($field), $doc.trim(), self.$field.to_string()),)*]
+            }
+            fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+                match name {
+                    $(stringify!($field) => {
+                        self.$field = value.parse().map_err(|_| {
+                            format!("invalid {} `{}`, expected a {}", name, value, stringify!($ty))
+                        })?;
+                    })*
+                    _ => return Err($crate::unknown_param(self, name)),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+/// Parameter values by name, from the command line ( `--param NAME=VALUE` )
+/// or from the config file of an input.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Settings {
+    values: Vec<(String, String)>,
+}
+
+impl Settings {
+    /// Removes the `--param NAME=VALUE` arguments from `args`.
+    pub fn take_args(args: &mut Vec<String>) -> Result<Settings, String> {
+        let mut values = vec![];
+        while let Some(i) = args.iter().position(|a| a == "--param") {
+            args.remove(i);
+            if i >= args.len() {
+                return Err(String::from("--param must be followed by NAME=VALUE"));
+            }
+            let setting = args.remove(i);
+            match setting.split_once('=') {
+                Some((name, value)) if !name.is_empty() => {
+                    values.push((name.to_string(), value.to_string()))
+                }
+                _ => return Err(format!("--param expects NAME=VALUE, not {}", setting)),
+            }
+        }
+        Ok(Settings { values })
+    }
+    /// Settings of (NAME, VALUE) pairs, like the query of a request.
+    pub fn from_pairs<N, V>(pairs: impl IntoIterator<Item = (N, V)>) -> Result<Settings, String>
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        let mut values = vec![];
+        for (name, value) in pairs {
+            let (name, value) = (name.into(), value.into());
+            if name.is_empty() {
+                return Err(format!("parameters are NAME=VALUE, not ={}", value));
+            }
+            values.push((name, value));
+        }
+        Ok(Settings { values })
+    }
+    /// Settings of a TOML config file, a table of parameter values.
+    pub fn parse(content: &str) -> Result<Settings, String> {
+        let table = content.parse::<toml::Table>().map_err(|e| e.to_string())?;
+        let mut values = vec![];
+        for (name, value) in table {
+            let value = match value {
+                toml::Value::String(s) => s,
+                toml::Value::Integer(i) => i.to_string(),
+                toml::Value::Float(f) => f.to_string(),
+                toml::Value::Boolean(b) => b.to_string(),
+                _ => {
+                    return Err(format!(
+                        "`{}` must be a string, a number or a boolean",
+                        name
+                    ))
+                }
+            };
+            values.push((name, value));
+        }
+        Ok(Settings { values })
+    }
+    /// Settings of the config file of an input file, the same name with a
+    /// `.toml` extension ( `day-15/example.toml` for `day-15/example.txt` ).
+    /// Empty without config file.
+    pub fn config(input: &str) -> Result<Settings, String> {
+        let path = Path::new(input).with_extension("toml");
+        if !path.is_file() {
+            return Ok(Settings::default());
+        }
+        fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| Settings::parse(&content))
+            .map_err(|e| format!("Cannot read '{}': {}", path.display(), e))
+    }
+    /// These settings, overridden by `other`.
+    pub fn merge(mut self, other: &Settings) -> Settings {
+        self.values.extend(other.values.iter().cloned());
+        self
+    }
+    /// Parameters from their defaults, changed by the settings.
+    pub fn apply<P: Params>(&self) -> Result<P, String> {
+        let mut params = P::default();
+        for (name, value) in self.values.iter() {
+            params.set(name, value)?;
+        }
+        Ok(params)
+    }
+}
+
+/// Error before solving the parts of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Unknown or invalid parameter, or parameter not fitting the input.
+    Param(String),
+    Parse(ParseError),
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Error {
+        Error::Parse(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Param(e) => write!(f, "{}", e),
+            Error::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::params! {
+        pub struct Test {
+            /// Rounds of part 1
+            pub rounds: u32 = 20,
+            /// Start valve
+            pub start: String = String::from("AA"),
+        }
+    }
+
+    #[test]
+    fn settings() {
+        let mut args = ["1", "--param", "rounds=3", "--param", "start=BB"]
+            .iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>();
+        let cli = Settings::take_args(&mut args).unwrap();
+        assert_eq!(args, vec!["1"]);
+        let params = cli.apply::<Test>().unwrap();
+        assert_eq!((params.rounds, params.start.as_str()), (3, "BB"));
+        assert_eq!(
+            Settings::from_pairs([("rounds", "3"), ("start", "BB")]),
+            Ok(cli.clone())
+        );
+        assert!(Settings::from_pairs([("", "3")]).is_err());
+
+        let config = Settings::parse("rounds = 10\nstart = \"CC\"\n").unwrap();
+        let params = config.merge(&cli).apply::<Test>().unwrap();
+        assert_eq!((params.rounds, params.start.as_str()), (3, "BB"));
+        assert_eq!(
+            Settings::default().apply::<Test>().unwrap(),
+            Test::default()
+        );
+
+        let bad = Settings::parse("rounds = -1").unwrap().apply::<Test>();
+        assert_eq!(bad.unwrap_err(), "invalid rounds `-1`, expected a u32");
+        assert!(Settings::parse("round = 1")
+            .unwrap()
+            .apply::<Test>()
+            .unwrap_err()
+            .contains("rounds = 20  ( Rounds of part 1 )"));
+        assert!(Settings::parse("round = 1").unwrap().apply::<()>().is_err());
+    }
+}
@@ -0,0 +1,301 @@
+use std::{error::Error, fmt, str::FromStr};
+
+/// Error of an input parser, located at a line and column of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Input file, set by the caller that knows it ( see `in_file` ).
+    pub file: Option<String>,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+    /// What the parser was expecting at this position.
+    pub expected: String,
+    /// What was found instead, empty at the end of a line.
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: &str, found: &str) -> ParseError {
+        ParseError {
+            file: None,
+            line,
+            column,
+            expected: expected.to_string(),
+            found: found.to_string(),
+        }
+    }
+    pub fn in_file(mut self, file: &str) -> ParseError {
+        self.file = Some(file.to_string());
+        self
+    }
+    /// Compiler-style rendering of the error, quoting the faulty line of `content`.
+    pub fn diagnostic(&self, content: &str) -> String {
+        let mut s = format!(
+            "error: expected {}, found {}\n",
+            self.expected,
+            self.found()
+        );
+        let margin = " ".repeat(self.line.to_string().len());
+        s += &format!(
+            "{}--> {}:{}:{}\n",
+            margin,
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column
+        );
+        if let Some(text) = content.lines().nth(self.line - 1) {
+            let underline = "^".repeat(self.found.chars().count().max(1));
+            s += &format!("{} |\n", margin);
+            s += &format!("{} | {}\n", self.line, text);
+            s += &format!(
+                "{} | {}{}\n",
+                margin,
+                " ".repeat(self.column - 1),
+                underline
+            );
+        }
+        s
+    }
+    fn found(&self) -> String {
+        if self.found.is_empty() {
+            String::from("end of line")
+        } else {
+            format!("`{}`", self.found)
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+        write!(
+            f,
+            "{}:{}: expected {}, found {}",
+            self.line,
+            self.column,
+            self.expected,
+            self.found()
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A piece of the input that knows its position, so that parsers can report
+/// where they failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub text: &'a str,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column of the first character, in characters.
+    pub column: usize,
+}
+
+/// The lines of `content`, as tokens. Inputs are normalized: the lines end
+/// with `\n` or `\r\n` and lose their trailing whitespace, the blank lines at
+/// the end of the input are dropped, and so is a byte order mark.
+pub fn lines(content: &str) -> impl Iterator<Item = Token<'_>> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let n_line = content
+        .lines()
+        .enumerate()
+        .filter(|(_, text)| !text.trim_end().is_empty())
+        .last()
+        .map_or(0, |(i, _)| i + 1);
+    content
+        .lines()
+        .take(n_line)
+        .enumerate()
+        .map(|(i, text)| Token {
+            text: text.trim_end(),
+            line: i + 1,
+            column: 1,
+        })
+}
+
+/// The sections of `content` separated by blank lines, as their lines. Any
+/// number of blank lines separate two sections, the blank lines at the start
+/// of the input are ignored too.
+pub fn sections(content: &str) -> Vec<Vec<Token<'_>>> {
+    let mut sections = vec![];
+    let mut section = vec![];
+    for line in lines(content) {
+        if !line.is_empty() {
+            section.push(line);
+        } else if !section.is_empty() {
+            sections.push(section);
+            section = vec![];
+        }
+    }
+    if !section.is_empty() {
+        sections.push(section);
+    }
+    sections
+}
+
+impl<'a> Token<'a> {
+    /// Sub-token from a slice of `self.text`.
+    fn sub(&self, text: &'a str) -> Token<'a> {
+        let offset = text.as_ptr() as usize - self.text.as_ptr() as usize;
+        Token {
+            text,
+            line: self.line,
+            column: self.column + self.text[..offset].chars().count(),
+        }
+    }
+    /// Empty token just after the end of `self`.
+    fn end(&self) -> Token<'a> {
+        self.sub(&self.text[self.text.len()..])
+    }
+    pub fn error(&self, expected: &str) -> ParseError {
+        ParseError::new(self.line, self.column, expected, self.text)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+    pub fn parse<T: FromStr>(&self, expected: &str) -> Result<T, ParseError> {
+        self.text.parse::<T>().map_err(|_| self.error(expected))
+    }
+    /// Whitespace separated words.
+    pub fn words(&self) -> Vec<Token<'a>> {
+        self.text.split_whitespace().map(|w| self.sub(w)).collect()
+    }
+    /// Parts separated by `sep`.
+    pub fn split(&self, sep: &str) -> Vec<Token<'a>> {
+        self.text.split(sep).map(|w| self.sub(w)).collect()
+    }
+    /// Exactly `n` parts separated by `sep`.
+    pub fn split_n(
+        &self,
+        sep: &str,
+        n: usize,
+        expected: &str,
+    ) -> Result<Vec<Token<'a>>, ParseError> {
+        let parts = self.split(sep);
+        if parts.len() == n {
+            Ok(parts)
+        } else if parts.len() < n {
+            Err(self.end().error(expected))
+        } else {
+            Err(self.error(expected))
+        }
+    }
+    /// The `i`-th whitespace separated word.
+    pub fn word(&self, i: usize, expected: &str) -> Result<Token<'a>, ParseError> {
+        let words = self.words();
+        match words.get(i) {
+            Some(w) => Ok(*w),
+            None => Err(words.last().unwrap_or(self).end().error(expected)),
+        }
+    }
+    /// Checks that the token is exactly `literal`.
+    pub fn expect(&self, literal: &str) -> Result<(), ParseError> {
+        if self.text == literal {
+            Ok(())
+        } else {
+            Err(self.error(&format!("`{}`", literal)))
+        }
+    }
+    /// The rest of the token after `prefix`.
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Token<'a>, ParseError> {
+        match self.text.strip_prefix(prefix) {
+            Some(rest) => Ok(self.sub(rest)),
+            None => {
+                let len = self
+                    .text
+                    .char_indices()
+                    .nth(prefix.chars().count())
+                    .map_or(self.text.len(), |(i, _)| i);
+                Err(self.sub(&self.text[..len]).error(&format!("`{}`", prefix)))
+            }
+        }
+    }
+    /// The token before `suffix`.
+    pub fn strip_suffix(&self, suffix: &str) -> Result<Token<'a>, ParseError> {
+        match self.text.strip_suffix(suffix) {
+            Some(rest) => Ok(self.sub(rest)),
+            None => Err(self.end().error(&format!("`{}`", suffix))),
+        }
+    }
+    /// The token without `suffix`, if present.
+    pub fn trim_end_matches(&self, suffix: char) -> Token<'a> {
+        self.sub(self.text.trim_end_matches(suffix))
+    }
+    pub fn trim_matches<F: Fn(char) -> bool>(&self, f: F) -> Token<'a> {
+        self.sub(self.text.trim_matches(f))
+    }
+    /// Parts ending with a character matching `f`, the last one may not.
+    pub fn split_inclusive<F: Fn(char) -> bool>(&self, f: F) -> Vec<Token<'a>> {
+        self.text.split_inclusive(f).map(|w| self.sub(w)).collect()
+    }
+    /// The token split in two at byte index `mid`.
+    pub fn split_at(&self, mid: usize) -> (Token<'a>, Token<'a>) {
+        let (a, b) = self.text.split_at(mid);
+        (self.sub(a), self.sub(b))
+    }
+    /// Each character as a one character token.
+    pub fn chars(&self) -> impl Iterator<Item = Token<'a>> + '_ {
+        self.text
+            .char_indices()
+            .map(|(i, c)| self.sub(&self.text[i..i + c.len_utf8()]))
+    }
+    /// Single character of the token.
+    pub fn char(&self) -> char {
+        self.text.chars().next().unwrap_or('\0')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_positions() {
+        let content = "first line\nmove 3 from 10 to 2";
+        let line = lines(content).nth(1).unwrap();
+        let word = line.word(3, "a stack").unwrap();
+        assert_eq!((word.text, word.line, word.column), ("10", 2, 13));
+        let err = line.word(6, "a stack").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 20, ""));
+    }
+
+    #[test]
+    fn normalized() {
+        let content = "\u{feff}1 2 \r\n\r\n \n\n3\t\r\n4\r\n\r\n\n";
+        let lines = lines(content).map(|l| l.text).collect::<Vec<_>>();
+        assert_eq!(lines, ["1 2", "", "", "", "3", "4"]);
+        let sections = sections(content);
+        let sections = sections
+            .iter()
+            .map(|s| s.iter().map(|l| (l.text, l.line)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(sections, [vec![("1 2", 1)], vec![("3", 5), ("4", 6)]]);
+        assert_eq!(super::sections("\n \n").len(), 0);
+    }
+
+    #[test]
+    fn diagnostic() {
+        let content = "x=3\nx=1b";
+        let line = lines(content).nth(1).unwrap();
+        let value = line.strip_prefix("x=").unwrap();
+        let err = value
+            .parse::<u32>("a number")
+            .unwrap_err()
+            .in_file("in.txt");
+        assert_eq!(err.to_string(), "in.txt:2:3: expected a number, found `1b`");
+        assert_eq!(
+            err.diagnostic(content),
+            concat!(
+                "error: expected a number, found `1b`\n",
+                " --> in.txt:2:3\n",
+                "  |\n",
+                "2 | x=1b\n",
+                "  |   ^^\n",
+            )
+        );
+    }
+}
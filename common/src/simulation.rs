@@ -0,0 +1,19 @@
+use crate::Image;
+
+/// A simulation of a part, stepped tick by tick by the terminal player
+/// ( `aoc play` ), or exported frame by frame ( `aoc image` ).
+pub trait Simulation {
+    /// Advances one tick, false once the simulation is over.
+    fn step(&mut self) -> bool;
+    /// Drawing of the current state.
+    fn draw(&self) -> String;
+    /// One line summary of the current state, like the count of the part so far.
+    fn status(&self) -> String;
+    /// Image of the current state, for the days exported as images.
+    fn image(&self) -> Option<Image> {
+        None
+    }
+}
+
+/// Builds the simulation of a part from its start, again on every replay.
+pub type Replay = Box<dyn Fn() -> Box<dyn Simulation>>;
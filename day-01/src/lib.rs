@@ -0,0 +1,88 @@
+use common::{explain, sections, ParseError};
+use log::info;
+
+pub fn parse(content: &str) -> Result<Vec<u32>, ParseError> {
+    let mut calories_per_elf = vec![];
+
+    // Count total calories per elf, separated by empty lines
+    for elf in sections(content) {
+        let mut calories = 0u32;
+        for line in elf {
+            calories = calories
+                .checked_add(line.parse::<u32>("a number of calories")?)
+                .ok_or_else(|| line.error("a total of calories below 2^32"))?;
+        }
+        calories_per_elf.push(calories);
+    }
+    info!("Number of elf {}", calories_per_elf.len());
+    Ok(calories_per_elf)
+}
+
+pub fn part1(calories_per_elf: &[u32]) -> u32 {
+    let (elf, max) = calories_per_elf
+        .iter()
+        .enumerate()
+        .max_by_key(|&(i, calories)| (calories, std::cmp::Reverse(i)))
+        .expect("No max");
+    explain!(
+        "Elf {} carries the most, {} calories, out of {} elves",
+        elf + 1,
+        max,
+        calories_per_elf.len()
+    );
+    *max
+}
+
+pub fn part2(calories_per_elf: &[u32]) -> u32 {
+    let mut calories_per_elf = calories_per_elf.iter().enumerate().collect::<Vec<_>>();
+    calories_per_elf.sort_by(|a, b| b.1.cmp(a.1));
+    let top = &calories_per_elf[..calories_per_elf.len().min(3)];
+    let total = top.iter().map(|(_, &calories)| calories).sum::<u32>();
+    explain!(
+        "Top 3: {} = {}",
+        top.iter()
+            .map(|(elf, calories)| format!("elf {} ( {} )", elf + 1, calories))
+            .collect::<Vec<_>>()
+            .join(" + "),
+        total
+    );
+    total
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<u32>;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(content: &str) -> Result<Vec<u32>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Vec<u32>, _: &()) -> u32 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<u32>, _: &()) -> u32 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 24000);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 45000);
+    }
+}
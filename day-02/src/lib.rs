@@ -0,0 +1,149 @@
+use common::{explain, lines, ParseError};
+
+const SHAPES: [&str; 3] = ["rock", "paper", "scissors"];
+const OUTCOMES: [&str; 3] = ["lose", "draw", "win"];
+
+// Index of a letter in SHAPES or OUTCOMES
+fn index(letter: &str) -> usize {
+    match letter {
+        "A" | "X" => 0,
+        "B" | "Y" => 1,
+        _ => 2,
+    }
+}
+
+pub fn parse(content: &str) -> Result<Vec<(String, String)>, ParseError> {
+    lines(content)
+        .map(|line| {
+            let entries = line.split_n(" ", 2, "two letters separated by a space")?;
+            let opponent = entries[0];
+            if !["A", "B", "C"].contains(&opponent.text) {
+                return Err(opponent.error("`A`, `B` or `C`"));
+            }
+            let mine = entries[1];
+            if !["X", "Y", "Z"].contains(&mine.text) {
+                return Err(mine.error("`X`, `Y` or `Z`"));
+            }
+            Ok((opponent.text.to_string(), mine.text.to_string()))
+        })
+        .collect()
+}
+
+pub fn part1(rounds: &[(String, String)]) -> u32 {
+    let mut total_score_a = 0u32;
+    for (opponent, me) in rounds.iter() {
+        let score_a1 = match (opponent.as_str(), me.as_str()) {
+            ("A", "X") => 3,
+            ("A", "Y") => 6,
+            ("A", "Z") => 0,
+            ("B", "X") => 0,
+            ("B", "Y") => 3,
+            ("B", "Z") => 6,
+            ("C", "X") => 6,
+            ("C", "Y") => 0,
+            ("C", "Z") => 3,
+            x => panic!("Unexpected oppoenent entry {:?}", x),
+        };
+        let score_a2 = match me.as_str() {
+            "X" => 1,
+            "Y" => 2,
+            "Z" => 3,
+            x => panic!("Unexpected me entry {}", x),
+        };
+        let score_a = score_a1 + score_a2;
+        total_score_a += score_a;
+        explain!(
+            "{} {}: {} against {}, {} {} + {} {} = {}, total {}",
+            opponent,
+            me,
+            SHAPES[index(me)],
+            SHAPES[index(opponent)],
+            OUTCOMES[score_a1 as usize / 3],
+            score_a1,
+            SHAPES[index(me)],
+            score_a2,
+            score_a,
+            total_score_a
+        );
+    }
+    total_score_a
+}
+
+pub fn part2(rounds: &[(String, String)]) -> u32 {
+    let mut total_score_b = 0u32;
+    for (opponent, me) in rounds.iter() {
+        let score_b1 = match (opponent.as_str(), me.as_str()) {
+            ("A", "X") => 3, // Scissor
+            ("A", "Y") => 1, // Rock
+            ("A", "Z") => 2, // Paper
+            ("B", "X") => 1, // Rock
+            ("B", "Y") => 2, // Paper
+            ("B", "Z") => 3, // Scissor
+            ("C", "X") => 2, // Paper
+            ("C", "Y") => 3, // Scissor
+            ("C", "Z") => 1, // Rock
+            x => panic!("Unexpected oppoenent entry {:?}", x),
+        };
+        let score_b2 = match me.as_str() {
+            "X" => 0, // Lose
+            "Y" => 3, // Draw
+            "Z" => 6, // Win
+            x => panic!("Unexpected me entry {}", x),
+        };
+        let score_b = score_b1 + score_b2;
+        total_score_b += score_b;
+        explain!(
+            "{} {}: {} against {} by playing {}, {} {} + {} {} = {}, total {}",
+            opponent,
+            me,
+            OUTCOMES[index(me)],
+            SHAPES[index(opponent)],
+            SHAPES[score_b1 as usize - 1],
+            OUTCOMES[index(me)],
+            score_b2,
+            SHAPES[score_b1 as usize - 1],
+            score_b1,
+            score_b,
+            total_score_b
+        );
+    }
+    total_score_b
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<(String, String)>;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(content: &str) -> Result<Vec<(String, String)>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Vec<(String, String)>, _: &()) -> u32 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<(String, String)>, _: &()) -> u32 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 15);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 12);
+    }
+}
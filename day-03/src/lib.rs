@@ -0,0 +1,129 @@
+use std::collections::HashSet;
+
+use common::{explain, lines, ParseError};
+use log::trace;
+
+pub fn parse(content: &str) -> Result<Vec<String>, ParseError> {
+    lines(content)
+        .map(|line| {
+            if let Some(c) = line.chars().find(|c| !c.char().is_ascii_alphabetic()) {
+                return Err(c.error("an item letter"));
+            }
+            if line.text.len() % 2 != 0 {
+                return Err(line.error("an even number of items"));
+            }
+            Ok(line.text.to_string())
+        })
+        .collect()
+}
+
+pub fn part1(lines: &[String]) -> u32 {
+    let mut total_priorities = 0u32;
+    for (i, line) in lines.iter().enumerate() {
+        let line_len = line.len();
+        if line_len % 2 != 0 {
+            panic!("Not an even line")
+        };
+        let first = &line[0..(line_len / 2)];
+        let second = &line[(line_len / 2)..];
+        let first_set = first.chars().collect::<HashSet<char>>();
+        let second_set = second.chars().collect::<HashSet<char>>();
+        trace!("First: {:?} ; Second: {:?}", first_set, second_set);
+        let commons = first_set.intersection(&second_set).collect::<HashSet<_>>();
+        assert!(
+            commons.len() == 1,
+            "More than one common item {:?}",
+            commons
+        );
+        let common = **(commons.iter().next().unwrap());
+        assert!(common.is_ascii());
+        let priority = if common.is_ascii_uppercase() {
+            (common as u32 - 'A' as u32) + 27
+        } else {
+            (common as u32 - 'a' as u32) + 1
+        };
+        trace!("Common {:?} = {} ", common, priority);
+        total_priorities += priority;
+        explain!(
+            "Rucksack {}: `{}` in both compartments, priority {}, total {}",
+            i + 1,
+            common,
+            priority,
+            total_priorities
+        );
+    }
+    total_priorities
+}
+
+pub fn part2(lines: &[String]) -> u32 {
+    let mut total_group_priorities = 0u32;
+    for (i, group_line) in lines.chunks(3).enumerate() {
+        let item_0 = group_line[0].chars().collect::<HashSet<char>>();
+        let item_1 = group_line[1].chars().collect::<HashSet<char>>();
+        let item_2 = group_line[2].chars().collect::<HashSet<char>>();
+        let commons_a = item_0
+            .intersection(&item_1)
+            .copied()
+            .collect::<HashSet<_>>();
+        let commons = commons_a.intersection(&item_2).collect::<HashSet<_>>();
+        assert!(
+            commons.len() == 1,
+            "More than one common item {:?}",
+            commons
+        );
+        let common = **(commons.iter().next().unwrap());
+        let priority = if common.is_ascii_uppercase() {
+            (common as u32 - 'A' as u32) + 27
+        } else {
+            (common as u32 - 'a' as u32) + 1
+        };
+        trace!("Group common {:?} = {} ", common, priority);
+        total_group_priorities += priority;
+        explain!(
+            "Group {}: badge `{}`, priority {}, total {}",
+            i + 1,
+            common,
+            priority,
+            total_group_priorities
+        );
+    }
+    total_group_priorities
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<String>;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(content: &str) -> Result<Vec<String>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Vec<String>, _: &()) -> u32 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<String>, _: &()) -> u32 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 157);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 70);
+    }
+}
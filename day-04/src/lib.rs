@@ -0,0 +1,104 @@
+use common::{explain, lines, ParseError, Token};
+
+// Section ranges of the two elves, as [start, end]
+pub type Pair = (Vec<u32>, Vec<u32>);
+
+pub fn parse(content: &str) -> Result<Vec<Pair>, ParseError> {
+    let mut pairs = vec![];
+    for line in lines(content) {
+        let elfs = line.split_n(",", 2, "two ranges separated by `,`")?;
+        let elf1_range = parse_range(elfs[0])?;
+        let elf2_range = parse_range(elfs[1])?;
+        pairs.push((elf1_range, elf2_range));
+    }
+    Ok(pairs)
+}
+
+fn parse_range(range: Token) -> Result<Vec<u32>, ParseError> {
+    range
+        .split_n("-", 2, "a range like `2-4`")?
+        .iter()
+        .map(|c| c.parse::<u32>("a section number"))
+        .collect()
+}
+
+pub fn part1(pairs: &[Pair]) -> u32 {
+    let mut total_included = 0u32;
+    for (i, (elf1_range, elf2_range)) in pairs.iter().enumerate() {
+        let included = ((elf1_range[0] <= elf2_range[0]) && (elf1_range[1] >= elf2_range[1]))
+            || ((elf2_range[0] <= elf1_range[0]) && (elf2_range[1] >= elf1_range[1]));
+        if included {
+            total_included += 1;
+            explain!(
+                "Pair {}: {}-{} and {}-{}, one contains the other, total {}",
+                i + 1,
+                elf1_range[0],
+                elf1_range[1],
+                elf2_range[0],
+                elf2_range[1],
+                total_included
+            );
+        }
+    }
+    total_included
+}
+
+pub fn part2(pairs: &[Pair]) -> u32 {
+    let mut total_overlap = 0u32;
+    for (i, (elf1_range, elf2_range)) in pairs.iter().enumerate() {
+        let overlap = !((elf1_range[1] < elf2_range[0]) || (elf1_range[0] > elf2_range[1]));
+        if overlap {
+            total_overlap += 1;
+            explain!(
+                "Pair {}: {}-{} and {}-{} overlap on {}-{}, total {}",
+                i + 1,
+                elf1_range[0],
+                elf1_range[1],
+                elf2_range[0],
+                elf2_range[1],
+                elf1_range[0].max(elf2_range[0]),
+                elf1_range[1].min(elf2_range[1]),
+                total_overlap
+            );
+        }
+    }
+    total_overlap
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<Pair>;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(content: &str) -> Result<Vec<Pair>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Vec<Pair>, _: &()) -> u32 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Pair>, _: &()) -> u32 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 2);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 4);
+    }
+}
@@ -0,0 +1,144 @@
+use common::{explain, sections, ParseError, Token};
+use log::{debug, trace};
+
+pub type Stacks = Vec<Vec<String>>;
+// (quantity, from, to)
+pub type Move = (usize, usize, usize);
+
+fn rearrange((stacks, moves): &(Stacks, Vec<Move>), reverse: bool) -> String {
+    let mut stacks = stacks.clone();
+    for &(qty, start, end) in moves.iter() {
+        let new_start_len = stacks[start].len() - qty;
+        let mut moved = stacks[start][new_start_len..].to_vec();
+        if reverse {
+            moved.reverse();
+        }
+        stacks[end].extend_from_slice(&moved);
+        stacks[start].truncate(new_start_len);
+        trace!(" -> stacks: {:?}", stacks);
+        explain!(
+            "Move {} from {} to {}: `{}` {}, tops `{}`",
+            qty,
+            start + 1,
+            end + 1,
+            moved.concat(),
+            if reverse { "one at a time" } else { "at once" },
+            stacks
+                .iter()
+                .map(|s| s.last().map_or(" ", |c| c.as_str()))
+                .collect::<String>()
+        );
+    }
+
+    debug!("End stacks: {:?}", stacks);
+    stacks
+        .iter_mut()
+        .map(|s| s.pop().unwrap())
+        .collect::<String>()
+}
+
+pub fn parse(content: &str) -> Result<(Stacks, Vec<Move>), ParseError> {
+    let sections = sections(content);
+    let Some(numbers) = sections.first().and_then(|drawing| drawing.last()) else {
+        return Err(ParseError::new(1, 1, "a stack drawing", ""));
+    };
+    let stack_s = &sections[0];
+    // The lines of the drawing lose their trailing spaces, only the last
+    // stack may have a crate after the end of the stack numbers line
+    let n_stack = numbers.words().len();
+    if let Some(line) = stack_s.iter().find(|l| l.text.len() > n_stack * 4 - 1) {
+        return Err(line.error("a line no wider than the stack numbers line"));
+    }
+    let mut stacks = vec![vec![]; n_stack];
+    for entry in stack_s.iter().take(stack_s.len() - 1) {
+        for (j, stack) in stacks.iter_mut().enumerate() {
+            let idx = j * 4 + 1;
+            let value = entry.text.get(idx..idx + 1).unwrap_or(" ").to_string();
+            if value != " " {
+                stack.insert(0, value);
+            }
+        }
+    }
+    debug!("Starting stacks: {:?}", stacks);
+
+    if let Some(extra) = sections.get(2) {
+        return Err(extra[0].error("the end of the moves"));
+    }
+    let mut moves = vec![];
+    for &line in sections.get(1).into_iter().flatten() {
+        trace!("Entry: {:?}", line);
+        line.word(0, "`move`")?.expect("move")?;
+        let qty = line.word(1, "a quantity")?.parse::<usize>("a quantity")?;
+        line.word(2, "`from`")?.expect("from")?;
+        let start = parse_stack(line.word(3, "a stack number")?, n_stack)?;
+        line.word(4, "`to`")?.expect("to")?;
+        let end = parse_stack(line.word(5, "a stack number")?, n_stack)?;
+        moves.push((qty, start, end));
+    }
+    Ok((stacks, moves))
+}
+
+// Stack numbers start at 1, returns the stack index
+fn parse_stack(word: Token, n_stack: usize) -> Result<usize, ParseError> {
+    let expected = format!("a stack number between 1 and {}", n_stack);
+    match word.parse::<usize>(&expected)? {
+        n if (1..=n_stack).contains(&n) => Ok(n - 1),
+        _ => Err(word.error(&expected)),
+    }
+}
+
+pub fn part1(input: &(Stacks, Vec<Move>)) -> String {
+    rearrange(input, true)
+}
+
+pub fn part2(input: &(Stacks, Vec<Move>)) -> String {
+    rearrange(input, false)
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = (Stacks, Vec<Move>);
+    type Params = ();
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(content: &str) -> Result<(Stacks, Vec<Move>), ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &(Stacks, Vec<Move>), _: &()) -> String {
+        part1(input)
+    }
+
+    fn part2(input: &(Stacks, Vec<Move>), _: &()) -> String {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), "CMZ");
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), "MCD");
+    }
+
+    #[test]
+    fn normalized_input() {
+        // Trimmed drawing lines, CRLF line endings and extra empty lines
+        let content = EXAMPLE.lines().map(str::trim_end).collect::<Vec<_>>();
+        let content = content.join("\r\n").replace(" 3\r\n\r\n", " 3\r\n\r\n\r\n");
+        assert_eq!(part1(&parse(&content).unwrap()), "CMZ");
+        let err = parse("[A]     [B]\n 1   2\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashSet;
+
+use common::{explain, lines, ParseError};
+
+fn first_marker(chars: &[char], exp_count: usize) -> usize {
+    for (i, entry) in chars.windows(exp_count).enumerate() {
+        let s = entry.iter().collect::<HashSet<_>>();
+        if s.len() == exp_count {
+            explain!(
+                "Characters {} to {}, `{}`, are all different",
+                i + 1,
+                i + exp_count,
+                entry.iter().collect::<String>()
+            );
+            return i + exp_count;
+        }
+    }
+    panic!("No {} different chars found", exp_count)
+}
+
+pub fn parse(content: &str) -> Result<Vec<char>, ParseError> {
+    let mut lines = lines(content);
+    let Some(line) = lines.next() else {
+        return Err(ParseError::new(1, 1, "a datastream", ""));
+    };
+    if let Some(c) = line.chars().find(|c| !c.char().is_ascii_lowercase()) {
+        return Err(c.error("a lowercase letter"));
+    }
+    if let Some(extra) = lines.find(|l| !l.is_empty()) {
+        return Err(extra.error("a single line"));
+    }
+    Ok(line.text.chars().collect::<Vec<_>>())
+}
+
+pub fn part1(chars: &[char]) -> usize {
+    first_marker(chars, 4)
+}
+
+pub fn part2(chars: &[char]) -> usize {
+    first_marker(chars, 14)
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<char>;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Vec<char>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Vec<char>, _: &()) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<char>, _: &()) -> usize {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE1: &str = include_str!("../example1.txt");
+    const EXAMPLE2: &str = include_str!("../example2.txt");
+    const EXAMPLE3: &str = include_str!("../example3.txt");
+    const EXAMPLE4: &str = include_str!("../example4.txt");
+    const EXAMPLE5: &str = include_str!("../example5.txt");
+
+    #[test]
+    fn example_1_part1() {
+        assert_eq!(part1(&parse(EXAMPLE1).unwrap()), 7);
+    }
+
+    #[test]
+    fn example_2_part1() {
+        assert_eq!(part1(&parse(EXAMPLE2).unwrap()), 5);
+    }
+
+    #[test]
+    fn example_3_part1() {
+        assert_eq!(part1(&parse(EXAMPLE3).unwrap()), 6);
+    }
+
+    #[test]
+    fn example_4_part1() {
+        assert_eq!(part1(&parse(EXAMPLE4).unwrap()), 10);
+    }
+
+    #[test]
+    fn example_5_part1() {
+        assert_eq!(part1(&parse(EXAMPLE5).unwrap()), 11);
+    }
+
+    #[test]
+    fn example_1_part2() {
+        assert_eq!(part2(&parse(EXAMPLE1).unwrap()), 19);
+    }
+
+    #[test]
+    fn example_2_part2() {
+        assert_eq!(part2(&parse(EXAMPLE2).unwrap()), 23);
+    }
+
+    #[test]
+    fn example_3_part2() {
+        assert_eq!(part2(&parse(EXAMPLE3).unwrap()), 23);
+    }
+
+    #[test]
+    fn example_4_part2() {
+        assert_eq!(part2(&parse(EXAMPLE4).unwrap()), 29);
+    }
+
+    #[test]
+    fn example_5_part2() {
+        assert_eq!(part2(&parse(EXAMPLE5).unwrap()), 26);
+    }
+}
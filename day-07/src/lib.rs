@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use common::{checked, explain, lines, ParseError};
+use log::{debug, info};
+
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub dir: bool,
+    pub nodes: HashMap<String, Node>,
+    pub size: u32,
+}
+impl Node {
+    pub fn new_dir() -> Node {
+        Node {
+            dir: true,
+            nodes: HashMap::new(),
+            size: 0,
+        }
+    }
+    pub fn new_file(size: u32) -> Node {
+        Node {
+            dir: false,
+            nodes: HashMap::new(),
+            size,
+        }
+    }
+    pub fn total_size(&self) -> u32 {
+        self.size_in(&mut vec![])
+    }
+    // Total size of the node at `path`
+    fn size_in<'a>(&'a self, path: &mut Vec<&'a str>) -> u32 {
+        self.nodes.iter().fold(self.size, |total, (name, node)| {
+            path.push(name);
+            let size = node.size_in(path);
+            path.pop();
+            checked::add(total, size, || format!("size of /{}", path.join("/")))
+        })
+    }
+    pub fn find_mut(&mut self, path: &[String]) -> Option<&mut Node> {
+        if path.is_empty() {
+            return Some(self);
+        }
+        self.nodes.get_mut(&path[0])?.find_mut(&path[1..])
+    }
+}
+pub fn parse(content: &str) -> Result<Node, ParseError> {
+    let mut cur_dir = vec![];
+    let mut top = Node::new_dir();
+
+    for line in lines(content) {
+        let first = line.word(0, "a command or a directory entry")?;
+        // cur_dir only goes through directories checked on `cd`
+        let cur_node = top.find_mut(&cur_dir).unwrap();
+        if first.text == "$" {
+            let command = line.word(1, "`cd` or `ls`")?;
+            if command.text == "ls" {
+                continue;
+            }
+            command.expect("cd")?;
+            let target = line.word(2, "a directory")?;
+            match target.text {
+                "/" => {
+                    cur_dir = vec![];
+                }
+                ".." => {
+                    cur_dir.pop();
+                }
+                name => {
+                    match cur_node.nodes.get(name) {
+                        Some(node) if node.dir => (),
+                        _ => return Err(target.error("a directory listed by `ls`")),
+                    }
+                    cur_dir.push(name.to_string());
+                }
+            }
+        } else {
+            let name = line.word(1, "a name")?.text.to_string();
+            match first.text {
+                "dir" => {
+                    cur_node.nodes.insert(name, Node::new_dir());
+                }
+                _ => {
+                    let size = first.parse::<u32>("`$`, `dir` or a file size")?;
+                    cur_node.nodes.insert(name, Node::new_file(size));
+                }
+            }
+        }
+    }
+    info!("Total size: {}", top.total_size());
+    Ok(top)
+}
+
+common::params! {
+    pub struct Params {
+        /// Largest directory size counted by part 1
+        pub size_limit: u32 = 100_000,
+        /// Size of the disk
+        pub system_size: u32 = 70_000_000,
+        /// Free space needed by the update
+        pub free_size: u32 = 30_000_000,
+    }
+}
+
+// Nodes of a directory, with their path
+fn children(path: &str, dir: Node) -> impl Iterator<Item = (String, Node)> + '_ {
+    dir.nodes
+        .into_iter()
+        .map(move |(name, node)| (format!("{}/{}", path, name), node))
+}
+
+pub fn part1(top: &Node, params: &Params) -> u32 {
+    let mut total_size = 0u32;
+
+    let mut stack = vec![(String::from(""), top.clone())];
+    while let Some((cur_name, cur_node)) = stack.pop() {
+        if !cur_node.dir {
+            continue;
+        }
+        let cur_size = cur_node.total_size();
+        if cur_size <= params.size_limit {
+            debug!("Found {}: {}", cur_name, cur_size);
+            let context = || String::from("sum of the small directories");
+            total_size = checked::add(total_size, cur_size, context);
+            explain!(
+                "Directory {}/: {}, total {}",
+                cur_name,
+                cur_size,
+                total_size
+            );
+        }
+        stack.extend(children(&cur_name, cur_node));
+    }
+    total_size
+}
+
+pub fn part2(top: &Node, params: &Params) -> u32 {
+    let used = top.total_size();
+    let free_size = match checked::enabled() {
+        true => checked::sub(params.system_size, used, || String::from("free space")),
+        false => params.system_size.saturating_sub(used),
+    };
+    let space_needed = params.free_size.saturating_sub(free_size);
+    info!("Space needed: {}", space_needed);
+
+    explain!(
+        "Free space: {} - {} used = {}, {} needed by the update: {} to free",
+        params.system_size,
+        used,
+        free_size,
+        params.free_size,
+        space_needed
+    );
+
+    let mut dir_size = vec![];
+    let mut stack = vec![(String::from(""), top.clone())];
+    while let Some((cur_name, cur_node)) = stack.pop() {
+        if !cur_node.dir {
+            continue;
+        }
+        let cur_size = cur_node.total_size();
+        if cur_size >= space_needed {
+            dir_size.push((cur_size, cur_name.clone()));
+        }
+        stack.extend(children(&cur_name, cur_node));
+    }
+    dir_size.sort();
+    let (size, name) = &dir_size[0];
+    explain!(
+        "Smallest directory of at least {}: {}/, {}, out of {} large enough",
+        space_needed,
+        name,
+        size,
+        dir_size.len()
+    );
+    *size
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Node;
+    type Params = Params;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(content: &str) -> Result<Node, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Node, params: &Params) -> u32 {
+        part1(input, params)
+    }
+
+    fn part2(input: &Node, params: &Params) -> u32 {
+        part2(input, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap(), &Params::default()), 95437);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            part2(&parse(EXAMPLE).unwrap(), &Params::default()),
+            24933642
+        );
+    }
+
+    #[test]
+    fn checked_overflow() {
+        let top = parse("$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n4000000000 b\n300000000 c\n");
+        checked::set(true);
+        let overflow = checked::catch(|| part1(&top.unwrap(), &Params::default()));
+        checked::set(false);
+        assert_eq!(overflow.unwrap_err().context, "size of /a");
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+
+use common::{explain, Image, ParseError};
+use grid::Grid;
+use log::{debug, info, trace};
+
+pub fn parse(content: &str) -> Result<Grid<i32>, ParseError> {
+    let trees = Grid::parse(content, "a tree height digit", |c| {
+        c.to_digit(10).map(|h| h as i32)
+    })?;
+    debug!("Trees: {:?}", trees);
+    Ok(trees)
+}
+
+pub fn part1(trees: &Grid<i32>) -> usize {
+    let height = trees.height() as i32;
+    let width = trees.width() as i32;
+    let mut visible = HashSet::new();
+    // Trees visible from the left, right, top and bottom
+    let mut seen = [0; 4];
+
+    for y in 0..height {
+        // Left to Right
+        let mut cur_size = -1i32;
+        for x in 0..width {
+            if trees[(x, y)] > cur_size {
+                visible.insert((y, x));
+                cur_size = trees[(x, y)];
+                seen[0] += 1;
+            }
+        }
+        // Right to left
+        let mut cur_size = -1i32;
+        for x in (0..width).rev() {
+            if trees[(x, y)] > cur_size {
+                visible.insert((y, x));
+                cur_size = trees[(x, y)];
+                seen[1] += 1;
+            }
+        }
+    }
+    for x in 0..width {
+        // Top to Bottom
+        let mut cur_size = -1i32;
+        for y in 0..height {
+            if trees[(x, y)] > cur_size {
+                visible.insert((y, x));
+                cur_size = trees[(x, y)];
+                seen[2] += 1;
+            }
+        }
+        // Bottom to top
+        let mut cur_size = -1i32;
+        for y in (0..height).rev() {
+            if trees[(x, y)] > cur_size {
+                visible.insert((y, x));
+                cur_size = trees[(x, y)];
+                seen[3] += 1;
+            }
+        }
+    }
+    explain!(
+        "Trees visible from the left: {}, the right: {}, the top: {}, the bottom: {}",
+        seen[0],
+        seen[1],
+        seen[2],
+        seen[3]
+    );
+    explain!(
+        "Visible from at least one side: {} of {} trees",
+        visible.len(),
+        height * width
+    );
+    visible.len()
+}
+
+pub fn part2(trees: &Grid<i32>) -> u32 {
+    let height = trees.height() as i32;
+    let width = trees.width() as i32;
+    let mut best_score = 0u32;
+    let mut best_tree = None;
+    for y in 0..height {
+        for x in 0..width {
+            trace!("DOING ({},{})", y, x);
+            let mut score = 1u32;
+            let size = trees[(x, y)];
+            // Viewing distances, right, left, down and up
+            let mut views = vec![];
+            // A tree on the edge has a viewing distance of 0 in that direction,
+            // so its scenic score is 0.
+
+            // Left to Right
+            let mut count = 0u32;
+            for xx in (x + 1)..width {
+                count += 1;
+                if trees[(xx, y)] >= size {
+                    break;
+                }
+            }
+            trace!(" LtR {}", count);
+            score *= count;
+            views.push(count);
+
+            // Right to Left
+            let mut count = 0u32;
+            for xx in (0..x).rev() {
+                count += 1;
+                if trees[(xx, y)] >= size {
+                    break;
+                }
+            }
+            score *= count;
+            views.push(count);
+            trace!(" RtL {}", count);
+
+            // Top to Bottom
+            let mut count = 0u32;
+            for yy in (y + 1)..height {
+                count += 1;
+                if trees[(x, yy)] >= size {
+                    break;
+                }
+            }
+            trace!(" TtB {}", count);
+            score *= count;
+            views.push(count);
+
+            // Bottom to Top
+            let mut count = 0u32;
+            for yy in (0..y).rev() {
+                count += 1;
+                if trees[(x, yy)] >= size {
+                    break;
+                }
+            }
+            trace!(" BtT {}", count);
+            score *= count;
+            views.push(count);
+            if score > best_score {
+                best_score = score;
+                best_tree = Some((y, x));
+                explain!(
+                    "Tree ({}, {}) of height {} sees {} right, {} left, {} down and {} up: score {}",
+                    x,
+                    y,
+                    size,
+                    views[0],
+                    views[1],
+                    views[2],
+                    views[3],
+                    score
+                );
+            }
+            trace!(" = {}", score);
+            best_score = best_score.max(score);
+        }
+    }
+    info!(
+        "Best score: {} at ({},{})",
+        best_score,
+        best_tree.unwrap().0,
+        best_tree.unwrap().1
+    );
+    best_score
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Grid<i32>;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = u32;
+
+    fn parse(content: &str) -> Result<Grid<i32>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Grid<i32>, _: &()) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Grid<i32>, _: &()) -> u32 {
+        part2(input)
+    }
+
+    fn images(input: &Grid<i32>, _: &(), _part: u8) -> Vec<(&'static str, Image)> {
+        vec![("heights", input.graymap(9, |&h| h as u16))]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE2: &str = include_str!("../example2.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 21);
+    }
+
+    #[test]
+    fn example_2_part1() {
+        assert_eq!(part1(&parse(EXAMPLE2).unwrap()), 31);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 8);
+    }
+
+    #[test]
+    fn example_2_part2() {
+        assert_eq!(part2(&parse(EXAMPLE2).unwrap()), 24);
+    }
+}
@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+
+use common::{explain, lines, ParseError, Simulation};
+use grid::SparseGrid;
+use log::{debug, trace};
+
+// Knots from the head, and the positions visited by the tail
+struct Rope {
+    knots: Vec<(i32, i32)>,
+    seen: HashSet<(i32, i32)>,
+}
+impl Rope {
+    fn new(n_knot: usize) -> Rope {
+        Rope {
+            knots: vec![(0, 0); n_knot],
+            seen: HashSet::new(),
+        }
+    }
+    fn pull(&mut self, m: &str) {
+        let rope = &mut self.knots;
+        let n_knot = rope.len();
+        rope[0] = match m {
+            "U" => (rope[0].0, rope[0].1 - 1),
+            "D" => (rope[0].0, rope[0].1 + 1),
+            "L" => (rope[0].0 - 1, rope[0].1),
+            "R" => (rope[0].0 + 1, rope[0].1),
+            _ => panic!(),
+        };
+        for i in 1..n_knot {
+            let diff = knot_move(i, (rope[i - 1].0 - rope[i].0, rope[i - 1].1 - rope[i].1));
+            rope[i] = (rope[i].0 + diff.0, rope[i].1 + diff.1);
+        }
+
+        self.seen.insert(rope[n_knot - 1]);
+        trace!("{} -> {:?}", m, rope);
+    }
+}
+
+fn simulate(moves: &[String], n_knot: usize) -> usize {
+    let mut rope = Rope::new(n_knot);
+    for (i, m) in moves.iter().enumerate() {
+        let n_seen = rope.seen.len();
+        rope.pull(m);
+        if rope.seen.len() > n_seen {
+            explain!(
+                "Step {} {}: head at {:?}, tail at new position {:?}, visited: {}",
+                i + 1,
+                m,
+                rope.knots[0],
+                rope.knots[n_knot - 1],
+                rope.seen.len()
+            );
+        }
+    }
+    rope.seen.len()
+}
+
+/// The rope pulled one step of the moves at a time.
+pub struct RopeSimulation {
+    moves: Vec<String>,
+    done: usize,
+    rope: Rope,
+}
+
+impl Simulation for RopeSimulation {
+    fn step(&mut self) -> bool {
+        let Some(m) = self.moves.get(self.done) else {
+            return false;
+        };
+        self.rope.pull(m);
+        self.done += 1;
+        true
+    }
+    // Head, knots and tail over the positions visited by the tail, from the
+    // start `s`
+    fn draw(&self) -> String {
+        let mut grid = SparseGrid::new();
+        for &pos in self.rope.seen.iter() {
+            grid.insert(pos, '#');
+        }
+        grid.insert((0, 0), 's');
+        let n_knot = self.rope.knots.len();
+        for (i, &pos) in self.rope.knots.iter().enumerate().rev() {
+            let knot = match i {
+                0 => 'H',
+                _ if i == n_knot - 1 => 'T',
+                _ => (b'0' + i as u8) as char,
+            };
+            grid.insert(pos, knot);
+        }
+        grid.render(|c| *c.unwrap_or(&'.'))
+    }
+    fn status(&self) -> String {
+        let next = self.moves.get(self.done).map_or("", |m| m.as_str());
+        format!(
+            "Step {}/{} {}  Tail positions: {}",
+            self.done,
+            self.moves.len(),
+            next,
+            self.rope.seen.len()
+        )
+    }
+}
+
+// Moves are expanded step by step, keep them short
+const MAX_STEPS: u32 = 1000;
+
+pub fn parse(content: &str) -> Result<Vec<String>, ParseError> {
+    let mut moves = vec![];
+    for line in lines(content) {
+        let content = line.split_n(" ", 2, "a direction and a count")?;
+        if !["U", "D", "L", "R"].contains(&content[0].text) {
+            return Err(content[0].error("`U`, `D`, `L` or `R`"));
+        }
+        let expected = format!("a step count up to {}", MAX_STEPS);
+        let count = content[1].parse::<u32>(&expected)?;
+        if count > MAX_STEPS {
+            return Err(content[1].error(&expected));
+        }
+        let dir = content[0].text.to_string();
+        (0..count).for_each(|_| moves.push(dir.clone()));
+    }
+    debug!("Moves: {:?}", moves);
+    Ok(moves)
+}
+
+pub fn part1(moves: &[String]) -> usize {
+    simulate(moves, 2)
+}
+
+pub fn part2(moves: &[String]) -> usize {
+    simulate(moves, 10)
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<String>;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Vec<String>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Vec<String>, _: &()) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<String>, _: &()) -> usize {
+        part2(input)
+    }
+
+    fn simulation(input: &Vec<String>, _: &(), part: u8) -> Option<Box<dyn Simulation>> {
+        let n_knot = if part == 1 { 2 } else { 10 };
+        Some(Box::new(RopeSimulation {
+            moves: input.clone(),
+            done: 0,
+            rope: Rope::new(n_knot),
+        }))
+    }
+}
+
+fn knot_move(_i: usize, diff: (i32, i32)) -> (i32, i32) {
+    assert!(diff.0.abs() <= 2);
+    assert!(diff.1.abs() <= 2);
+    if diff.0.abs() != 2 && diff.1.abs() != 2 {
+        (0, 0)
+    } else {
+        let d0 = if diff.0.abs() == 2 {
+            diff.0 / 2
+        } else {
+            diff.0
+        };
+        let d1 = if diff.1.abs() == 2 {
+            diff.1 / 2
+        } else {
+            diff.1
+        };
+        (d0, d1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE2: &str = include_str!("../example2.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn example_2_part1() {
+        assert_eq!(part1(&parse(EXAMPLE2).unwrap()), 88);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 1);
+    }
+
+    #[test]
+    fn example_2_part2() {
+        assert_eq!(part2(&parse(EXAMPLE2).unwrap()), 36);
+    }
+}
@@ -0,0 +1,166 @@
+use common::{explain, lines, ParseError, Simulation};
+use log::{debug, trace};
+
+pub fn parse(content: &str) -> Result<Vec<i32>, ParseError> {
+    let mut x_t = vec![1i32];
+    for line in lines(content) {
+        let x = *(x_t.last().unwrap());
+        if line.text == "noop" {
+            x_t.push(x);
+        } else {
+            let content = line.split_n(" ", 2, "`noop` or `addx V`")?;
+            content[0].expect("addx")?;
+            let v = content[1].parse::<i32>("a value to add")?;
+            let Some(next) = x.checked_add(v) else {
+                return Err(content[1].error("a value keeping X within 32 bits"));
+            };
+            x_t.push(x);
+            x_t.push(next);
+        }
+    }
+    Ok(x_t)
+}
+
+pub fn part1(x_t: &[i32]) -> i32 {
+    let mut total = 0;
+    for i in (20..=220).step_by(40) {
+        debug!("{:3}th : {}", i, x_t[i - 1]);
+        total += i as i32 * x_t[i - 1];
+        explain!(
+            "Cycle {}: X = {}, strength {} * {} = {}, total {}",
+            i,
+            x_t[i - 1],
+            i,
+            x_t[i - 1],
+            i as i32 * x_t[i - 1],
+            total
+        );
+    }
+    total
+}
+
+pub fn part2(x_t: &[i32]) -> String {
+    let crt = draw_crt(x_t, x_t.len() - 1);
+    for (row, pixels) in crt.lines().enumerate() {
+        let (start, end) = (row * 40 + 1, row * 40 + pixels.len());
+        // Sprite at the start of the row, then at each move
+        let moves = (start..=end)
+            .filter(|&i| i == start || x_t[i - 1] != x_t[i - 2])
+            .map(|i| format!("{} from cycle {}", x_t[i - 1], i))
+            .collect::<Vec<_>>();
+        explain!("Row `{}`, sprite at X = {}", pixels, moves.join(", "));
+    }
+    crt
+}
+
+// Pixels drawn by the CRT during the first cycles
+fn draw_crt(x_t: &[i32], n_cycle: usize) -> String {
+    let mut idx = 1;
+    let mut crt = String::from("");
+    while idx <= n_cycle {
+        let crt_x = (idx - 1) % 40;
+        let c = if (x_t[idx - 1] - crt_x as i32).abs() <= 1 {
+            "#"
+        } else {
+            "."
+        };
+        crt += c;
+        if idx < 21 {
+            trace!("i: {}, crt_x: {} x:{}", idx, crt_x, x_t[idx - 1],);
+            trace!("CRT: {}", crt);
+        }
+        if idx % 40 == 0 {
+            crt += "\n";
+        }
+        idx += 1;
+    }
+    crt
+}
+
+/// The CRT drawing one pixel a cycle, under the sprite.
+pub struct CrtSimulation {
+    x_t: Vec<i32>,
+    cycle: usize,
+}
+
+impl Simulation for CrtSimulation {
+    fn step(&mut self) -> bool {
+        if self.cycle + 1 >= self.x_t.len() {
+            return false;
+        }
+        self.cycle += 1;
+        true
+    }
+    fn draw(&self) -> String {
+        let x = self.x_t[self.cycle];
+        let sprite = (0..40)
+            .map(|i| if (x - i).abs() <= 1 { '#' } else { '.' })
+            .collect::<String>();
+        format!("Sprite: {}\n\n{}", sprite, draw_crt(&self.x_t, self.cycle))
+    }
+    fn status(&self) -> String {
+        let strength = (20..=self.cycle.min(220))
+            .step_by(40)
+            .map(|i| i as i32 * self.x_t[i - 1])
+            .sum::<i32>();
+        format!(
+            "Cycle {}  X: {}  Signal strength: {}",
+            self.cycle, self.x_t[self.cycle], strength
+        )
+    }
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<i32>;
+    type Params = ();
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn parse(content: &str) -> Result<Vec<i32>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Vec<i32>, _: &()) -> i32 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<i32>, _: &()) -> String {
+        part2(input)
+    }
+
+    fn simulation(input: &Vec<i32>, _: &(), _: u8) -> Option<Box<dyn Simulation>> {
+        Some(Box::new(CrtSimulation {
+            x_t: input.clone(),
+            cycle: 0,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 13140);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            part2(&parse(EXAMPLE).unwrap()),
+            concat!(
+                "##..##..##..##..##..##..##..##..##..##..\n",
+                "###...###...###...###...###...###...###.\n",
+                "####....####....####....####....####....\n",
+                "#####.....#####.....#####.....#####.....\n",
+                "######......######......######......####\n",
+                "#######.......#######.......#######.....\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use common::{checked, explain, sections, ParseError, Token};
+use log::{debug, trace};
+
+#[derive(Default, Clone, Debug)]
+pub enum Operation {
+    Add(u64),
+    Mul(u64),
+    Square,
+    #[default]
+    None,
+}
+impl Operation {
+    pub fn parse(op: Token, value: Token) -> Result<Operation, ParseError> {
+        match op.text {
+            "+" => Ok(Operation::Add(value.parse::<u64>("a number")?)),
+            "*" => match value.text {
+                "old" => Ok(Operation::Square),
+                _ => Ok(Operation::Mul(value.parse::<u64>("a number or `old`")?)),
+            },
+            _ => Err(op.error("`+` or `*`")),
+        }
+    }
+    /// New worry level from `v`, when inspected by `monkey`.
+    pub fn apply(&self, v: u64, monkey: usize) -> u64 {
+        let context = || format!("operation of monkey {}", monkey);
+        match self {
+            Operation::Add(x) => checked::add(v, *x, context),
+            Operation::Mul(x) => checked::mul(v, *x, context),
+            Operation::Square => checked::mul(v, v, context),
+            Operation::None => panic!(),
+        }
+    }
+}
+#[derive(Default, Clone, Debug)]
+pub struct Monkey {
+    pub items: Vec<u64>,
+    pub operation: Operation,
+    pub divisability: u64,
+    pub true_dest: u64,
+    pub false_dest: u64,
+    pub inspect_count: u64,
+}
+fn monkey_business(monkeys: &[Monkey], n_round: u32, div_3: bool) -> u64 {
+    let mut monkeys = monkeys.to_vec();
+    let context = || String::from("product of the divisors");
+    let modulo = monkeys.iter().fold(2, |product, m| {
+        checked::mul(product, m.divisability, context)
+    });
+
+    for round in 0..n_round {
+        for i in 0..monkeys.len() {
+            let monkey = &mut monkeys[i];
+            let mut moves = vec![];
+            for &item in monkey.items.iter() {
+                let mut worry = monkey.operation.apply(item, i);
+                if div_3 {
+                    worry /= 3
+                };
+                let worry = worry % modulo;
+                let dest = if worry.is_multiple_of(monkey.divisability) {
+                    monkey.true_dest
+                } else {
+                    monkey.false_dest
+                };
+                moves.push((dest, worry));
+                monkey.inspect_count += 1;
+            }
+            monkey.items = vec![];
+            for (dest, item) in moves.into_iter() {
+                monkeys[dest as usize].items.push(item);
+            }
+        }
+        debug!("After round: {}", round);
+        // Reported like the puzzle: after the rounds 1, 20 and every 1000
+        let done = round + 1;
+        if done == 1 || done == 20 || done % 1000 == 0 || done == n_round {
+            let counts = monkeys.iter().map(|m| m.inspect_count.to_string());
+            explain!(
+                "After round {}, inspections by monkey: {}",
+                done,
+                counts.collect::<Vec<_>>().join(", ")
+            );
+        }
+        for (i, monkey) in monkeys.iter().enumerate() {
+            trace!("   {}: {:?}", i, monkey);
+        }
+    }
+    let mut counts = monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, m)| (m.inspect_count, i))
+        .collect::<Vec<_>>();
+    counts.sort_by(|a, b| b.cmp(a));
+    let business = checked::mul(counts[0].0, counts[1].0, || String::from("monkey business"));
+    explain!(
+        "Most active: monkey {} ( {} ) and monkey {} ( {} ), business {}",
+        counts[0].1,
+        counts[0].0,
+        counts[1].1,
+        counts[1].0,
+        business
+    );
+    business
+}
+
+pub fn parse(content: &str) -> Result<Vec<Monkey>, ParseError> {
+    let mut monkeys = vec![];
+    // A monkey by section, the last one may not end with an empty line
+    for section in sections(content) {
+        let mut monkey = Monkey::default();
+        for line in section {
+            let content = line.words();
+            match content[0].text {
+                "Monkey" => (),
+                "Starting" => {
+                    line.word(1, "`items:`")?.expect("items:")?;
+                    monkey.items = content
+                        .iter()
+                        .skip(2)
+                        .map(|s| s.trim_end_matches(',').parse::<u64>("a worry level"))
+                        .collect::<Result<Vec<_>, _>>()?;
+                }
+                "Operation:" => {
+                    line.word(3, "`old`")?.expect("old")?;
+                    monkey.operation = Operation::parse(
+                        line.word(4, "an operator")?,
+                        line.word(5, "an operand")?,
+                    )?;
+                }
+                "Test:" => {
+                    monkey.divisability = line.word(3, "a divisor")?.parse::<u64>("a divisor")?;
+                }
+                "If" => {
+                    let dest = line
+                        .word(5, "a monkey number")?
+                        .parse::<u64>("a monkey number")?;
+                    match content[1].text {
+                        "true:" => monkey.true_dest = dest,
+                        "false:" => monkey.false_dest = dest,
+                        _ => return Err(content[1].error("`true:` or `false:`")),
+                    }
+                }
+                _ => {
+                    return Err(
+                        content[0].error("`Monkey`, `Starting`, `Operation:`, `Test:` or `If`")
+                    )
+                }
+            }
+        }
+        monkeys.push(monkey);
+    }
+
+    for (i, monkey) in monkeys.iter().enumerate() {
+        debug!("{}: {:?}", i, monkey);
+    }
+    Ok(monkeys)
+}
+
+common::params! {
+    pub struct Params {
+        /// Rounds of part 1, with the worry divided by 3
+        pub rounds1: u32 = 20,
+        /// Rounds of part 2
+        pub rounds2: u32 = 10000,
+    }
+}
+
+pub fn part1(monkeys: &[Monkey], params: &Params) -> u64 {
+    monkey_business(monkeys, params.rounds1, true)
+}
+
+pub fn part2(monkeys: &[Monkey], params: &Params) -> u64 {
+    monkey_business(monkeys, params.rounds2, false)
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<Monkey>;
+    type Params = Params;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(content: &str) -> Result<Vec<Monkey>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Vec<Monkey>, params: &Params) -> u64 {
+        part1(input, params)
+    }
+
+    fn part2(input: &Vec<Monkey>, params: &Params) -> u64 {
+        part2(input, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap(), &Params::default()), 10605);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            part2(&parse(EXAMPLE).unwrap(), &Params::default()),
+            2713310158
+        );
+    }
+
+    #[test]
+    fn last_monkey() {
+        // Without the empty line after the last monkey, with CRLF line endings
+        let content = EXAMPLE.trim_end().replace('\n', "\r\n");
+        let monkeys = parse(&content).unwrap();
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(part1(&monkeys, &Params::default()), 10605);
+    }
+
+    #[test]
+    fn checked_overflow() {
+        let mut monkeys = parse(EXAMPLE).unwrap();
+        monkeys[2].divisability = 1 << 31;
+        checked::set(true);
+        let overflow = checked::catch(|| part2(&monkeys, &Params::default())).unwrap_err();
+        checked::set(false);
+        assert_eq!(overflow.context, "operation of monkey 2");
+    }
+}
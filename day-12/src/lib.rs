@@ -0,0 +1,208 @@
+use std::collections::VecDeque;
+
+use common::{explain, Image, ParseError};
+use grid::{Grid, Pos};
+use log::{debug, info};
+use search::Problem;
+
+#[derive(Clone, Debug)]
+pub struct HeightMap {
+    pub heights: Grid<u32>,
+    pub start: Pos,
+    pub end: Pos,
+}
+
+impl HeightMap {
+    // Searched backward, from the end down to any of the starts
+    pub fn shortest(&self, starts: &[Pos]) -> u32 {
+        info!("All Starts {:?}", starts.len());
+        let (path, stats) = search::bfs(&Climb { map: self, starts }, [self.end]);
+        debug!("{} positions explored", stats.iterations);
+        let path = path.expect("no path from a start to the end");
+        if explain::enabled() {
+            self.explain_path(&path.states, stats.iterations);
+        }
+        path.cost as u32
+    }
+    // From the start up to the end, where the height changes
+    fn explain_path(&self, states: &[Pos], explored: u64) {
+        let letter = |pos: Pos| (b'a' + self.heights[pos] as u8) as char;
+        let start = *states.last().unwrap();
+        explain!(
+            "Start at {:?} `{}`, {} steps to the end, {} positions explored",
+            start,
+            letter(start),
+            states.len() - 1,
+            explored
+        );
+        let path = states.iter().rev().collect::<Vec<_>>();
+        for (step, pair) in path.windows(2).enumerate() {
+            if self.heights[*pair[1]] != self.heights[*pair[0]] {
+                explain!("Step {}: {:?} `{}`", step + 1, pair[1], letter(*pair[1]));
+            }
+        }
+    }
+    /// Steps from every position to the end, None where the end cannot be
+    /// reached.
+    pub fn distances(&self) -> Grid<Option<u32>> {
+        let climb = Climb {
+            map: self,
+            starts: &[],
+        };
+        let mut distances = self.heights.map(|_| None);
+        distances[self.end] = Some(0);
+        let mut queue = VecDeque::from([self.end]);
+        while let Some(pos) = queue.pop_front() {
+            let distance = distances[pos].unwrap();
+            for (next, _) in climb.successors(&pos) {
+                if distances[next].is_none() {
+                    distances[next] = Some(distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+    }
+    /// The heights, from black at `a` to white at `z`, and the distances to
+    /// the end, white near the end down to black where it cannot be reached.
+    pub fn images(&self) -> Vec<(&'static str, Image)> {
+        let distances = self.distances();
+        let far = distances.iter().filter_map(|(_, &d)| d).max().unwrap_or(0) + 1;
+        vec![
+            ("heights", self.heights.graymap(25, |&h| h as u16)),
+            (
+                "distances",
+                distances.graymap(far as u16, |&d| d.map_or(0, |d| (far - d) as u16)),
+            ),
+        ]
+    }
+}
+
+struct Climb<'a> {
+    map: &'a HeightMap,
+    starts: &'a [Pos],
+}
+
+impl Problem for Climb<'_> {
+    type State = Pos;
+    type Key = Pos;
+
+    fn successors(&self, &pos: &Pos) -> Vec<(Pos, u64)> {
+        let heights = &self.map.heights;
+        heights
+            .neighbours4(pos)
+            .filter(|&next| heights[next] + 1 >= heights[pos])
+            .map(|next| (next, 1))
+            .collect()
+    }
+    fn is_goal(&self, pos: &Pos) -> bool {
+        self.starts.contains(pos)
+    }
+    fn key(&self, &pos: &Pos) -> Pos {
+        pos
+    }
+}
+
+pub fn parse(content: &str) -> Result<HeightMap, ParseError> {
+    let expected = "a height letter, or a single `S` and `E`";
+    let map = Grid::parse(content, expected, |c| match c {
+        'S' | 'E' | 'a'..='z' => Some(c),
+        _ => None,
+    })?;
+    let mut marks = [None, None];
+    for ((x, y), &c) in map.iter() {
+        let mark = match c {
+            'S' => &mut marks[0],
+            'E' => &mut marks[1],
+            _ => continue,
+        };
+        if mark.is_some() {
+            let (line, column) = (y as usize + 1, x as usize + 1);
+            return Err(ParseError::new(line, column, expected, &c.to_string()));
+        }
+        *mark = Some((x, y));
+    }
+    let [Some(start), Some(end)] = marks else {
+        return Err(ParseError::new(
+            map.height() + 1,
+            1,
+            "a map with `S` and `E`",
+            "",
+        ));
+    };
+    let heights = map.map(|&c| match c {
+        'S' => 0,
+        'E' => 25,
+        _ => c as u32 - 'a' as u32,
+    });
+    debug!("Heights: {:?}", heights);
+    debug!("End {:?}", end);
+    Ok(HeightMap {
+        heights,
+        start,
+        end,
+    })
+}
+
+pub fn part1(map: &HeightMap) -> u32 {
+    map.shortest(&[map.start])
+}
+
+pub fn part2(map: &HeightMap) -> u32 {
+    let starts = map
+        .heights
+        .iter()
+        .filter_map(|(pos, &h)| if h == 0 { Some(pos) } else { None })
+        .collect::<Vec<_>>();
+    map.shortest(&starts)
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = HeightMap;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(content: &str) -> Result<HeightMap, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &HeightMap, _: &()) -> u32 {
+        part1(input)
+    }
+
+    fn part2(input: &HeightMap, _: &()) -> u32 {
+        part2(input)
+    }
+
+    fn images(input: &HeightMap, _: &(), _part: u8) -> Vec<(&'static str, Image)> {
+        input.images()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 31);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 29);
+    }
+
+    #[test]
+    fn example_distances() {
+        let map = parse(EXAMPLE).unwrap();
+        let distances = map.distances();
+        assert_eq!(distances[map.start], Some(31));
+        assert_eq!(distances[map.end], Some(0));
+    }
+}
@@ -0,0 +1,193 @@
+use std::{cmp::Ordering, fmt};
+
+use common::{explain, sections, ParseError, Token};
+use json::JsonValue;
+use log::debug;
+
+const MAX_DEPTH: i32 = 100;
+
+#[derive(Debug, Clone)]
+pub enum Entry {
+    Value(u32),
+    List(Vec<Entry>),
+}
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Value(l0), Self::Value(r0)) => l0 == r0,
+            (Self::List(l0), Self::List(r0)) => l0 == r0,
+            _ => false,
+        }
+    }
+}
+impl Eq for Entry {}
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Entry::Value(u), Entry::Value(v)) => u.cmp(v),
+            (Entry::Value(_), Entry::List(v)) => vec![self.clone()].cmp(v),
+            (Entry::List(u), Entry::Value(_)) => u.cmp(&vec![other.clone()]),
+            (Entry::List(u), Entry::List(v)) => u.cmp(v),
+        }
+    }
+}
+/// The packet as written in the input.
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Entry::Value(v) => write!(f, "{}", v),
+            Entry::List(entries) => {
+                let entries = entries.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+                write!(f, "[{}]", entries.join(","))
+            }
+        }
+    }
+}
+impl Entry {
+    // The first difference deciding the order, None if equal
+    fn decision(&self, other: &Entry) -> Option<String> {
+        match (self, other) {
+            (Entry::Value(u), Entry::Value(v)) => (u != v).then(|| format!("{} against {}", u, v)),
+            (Entry::Value(_), Entry::List(_)) => Entry::List(vec![self.clone()]).decision(other),
+            (Entry::List(_), Entry::Value(_)) => self.decision(&Entry::List(vec![other.clone()])),
+            (Entry::List(u), Entry::List(v)) => u
+                .iter()
+                .zip(v)
+                .find_map(|(a, b)| a.decision(b))
+                .or_else(|| {
+                    (u.len() != v.len()).then(|| format!("{} against {} items", u.len(), v.len()))
+                }),
+        }
+    }
+    /// None if the JSON value is not made of lists and integers.
+    pub fn from_json(js: &JsonValue) -> Option<Entry> {
+        match js {
+            JsonValue::Number(_) => Some(Entry::Value(js.as_u32()?)),
+            JsonValue::Array(v) => Some(Entry::List(
+                v.iter().map(Entry::from_json).collect::<Option<Vec<_>>>()?,
+            )),
+            _ => None,
+        }
+    }
+    pub fn parse(line: Token) -> Result<Entry, ParseError> {
+        let expected = "a packet made of lists and integers";
+        // Entries are compared and dropped recursively
+        let mut depth = 0;
+        for c in line.chars() {
+            match c.char() {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => continue,
+            }
+            if depth > MAX_DEPTH {
+                return Err(c.error(&format!("lists nested at most {} deep", MAX_DEPTH)));
+            }
+        }
+        let js = json::parse(line.text).map_err(|e| match e {
+            json::Error::UnexpectedCharacter {
+                line: 1, column, ..
+            } => line.chars().nth(column - 1).unwrap_or(line).error(expected),
+            _ => line.error(expected),
+        })?;
+        if !js.is_array() {
+            return Err(line.error("a list"));
+        }
+        Entry::from_json(&js).ok_or_else(|| line.error(expected))
+    }
+}
+pub fn parse(content: &str) -> Result<Vec<Entry>, ParseError> {
+    let mut entries = vec![];
+    // Pairs of packets, separated by empty lines
+    for pair in sections(content) {
+        match pair[..] {
+            [first, second] => entries.extend([Entry::parse(first)?, Entry::parse(second)?]),
+            [first] => return Err(ParseError::new(first.line + 1, 1, "a second packet", "")),
+            _ => return Err(pair[2].error("an empty line after a pair")),
+        }
+    }
+    Ok(entries)
+}
+
+pub fn part1(entries: &[Entry]) -> usize {
+    let mut count = 0;
+    for (i, e) in entries.chunks(2).enumerate() {
+        let right = e[0] < e[1];
+        if right {
+            count += i + 1;
+        }
+        explain!(
+            "Pair {}: {} and {}, {}: {} order, total {}",
+            i + 1,
+            e[0],
+            e[1],
+            e[0].decision(&e[1])
+                .unwrap_or_else(|| String::from("equal")),
+            if right { "right" } else { "wrong" },
+            count
+        );
+    }
+    count
+}
+
+pub fn part2(entries: &[Entry]) -> usize {
+    let mut entries = entries.to_vec();
+    let code0 = Entry::List(vec![Entry::Value(2)]);
+    let code1 = Entry::List(vec![Entry::Value(6)]);
+    entries.push(code0.clone());
+    entries.push(code1.clone());
+    entries.sort();
+    let i0 = entries.iter().position(|c| c == &code0).unwrap() + 1;
+    let i1 = entries.iter().position(|c| c == &code1).unwrap() + 1;
+    debug!("First: {:?}", entries.first());
+    debug!("Last: {:?}", entries.last());
+    explain!(
+        "Dividers [[2]] and [[6]] sorted at {} and {} of {} packets",
+        i0,
+        i1,
+        entries.len()
+    );
+    i0 * i1
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<Entry>;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Vec<Entry>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Vec<Entry>, _: &()) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Entry>, _: &()) -> usize {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 140);
+    }
+}
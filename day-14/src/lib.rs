@@ -0,0 +1,234 @@
+use common::{explain, lines, Image, ParseError, Simulation, Token};
+use grid::SparseGrid;
+use log::{debug, info, trace};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Rock,
+    Sand,
+}
+
+#[derive(Debug, Clone)]
+pub struct World {
+    pub blocks: SparseGrid<Cell>,
+    pub abyss_y: i32,
+    pub sand_count: u32,
+}
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl World {
+    pub fn new() -> World {
+        World {
+            blocks: SparseGrid::new(),
+            abyss_y: i32::MIN,
+            sand_count: 0,
+        }
+    }
+    pub fn add_rockline(&mut self, x0: i32, y0: i32, x1: i32, y1: i32) {
+        assert!(x0 == x1 || y0 == y1, "Not a line");
+        self.abyss_y = self.abyss_y.max(y0 + 1).max(y1 + 1);
+        if x0 == x1 {
+            for y in (y0.min(y1))..=(y0.max(y1)) {
+                self.blocks.insert((x0, y), Cell::Rock);
+            }
+        } else {
+            for x in (x0.min(x1))..=(x0.max(x1)) {
+                self.blocks.insert((x, y0), Cell::Rock);
+            }
+        }
+    }
+    pub fn add_floor(&mut self) {
+        let ((min_x, _), (max_x, _)) = self.blocks.bounds().unwrap();
+        let x0 = min_x - self.abyss_y;
+        let x1 = max_x + self.abyss_y;
+        let y = self.abyss_y + 1; // Abyss is already max_y +1;
+        self.add_rockline(x0, y, x1, y);
+    }
+    pub fn add_sand(&mut self, x: i32, y: i32) {
+        trace!("New sand: ({},{}): ", x, y);
+        self.blocks.insert((x, y), Cell::Sand);
+
+        self.sand_count += 1;
+    }
+    pub fn is_blocked(&self, x: i32, y: i32) -> bool {
+        self.blocks.contains((x, y))
+    }
+    pub fn draw(&self) -> String {
+        self.blocks.render(|cell| match cell {
+            Some(Cell::Rock) => '#',
+            Some(Cell::Sand) => 'o',
+            None => '.',
+        })
+    }
+    pub fn drop_sand(&mut self) -> u32 {
+        while self.drop_one() {}
+        self.sand_count
+    }
+    /// Drops a new sand on (500,0), false when it falls in the abyss or the
+    /// source is blocked
+    pub fn drop_one(&mut self) -> bool {
+        let (mut x, mut y) = (500, 0);
+        if self.is_blocked(x, y) {
+            return false;
+        }
+        loop {
+            if !self.is_blocked(x, y + 1) {
+                y += 1;
+                if y == self.abyss_y {
+                    info!("Stopped on abyss");
+                    explain!(
+                        "Sand {} falls into the abyss below ({}, {})",
+                        self.sand_count + 1,
+                        x,
+                        y - 1
+                    );
+                    return false;
+                }
+            } else if !self.is_blocked(x - 1, y + 1) {
+                x -= 1;
+                y += 1;
+            } else if !self.is_blocked(x + 1, y + 1) {
+                x += 1;
+                y += 1;
+            } else {
+                self.add_sand(x, y);
+                if (x, y) == (500, 0) {
+                    info!("Stopped on initial");
+                    explain!("Sand {} comes to rest on the source", self.sand_count);
+                }
+                return true;
+            }
+        }
+    }
+}
+
+/// The sand dropped one unit at a time.
+pub struct SandSimulation {
+    world: World,
+}
+
+impl Simulation for SandSimulation {
+    fn step(&mut self) -> bool {
+        self.world.drop_one()
+    }
+    fn draw(&self) -> String {
+        self.world.draw()
+    }
+    fn status(&self) -> String {
+        format!("Sand at rest: {}", self.world.sand_count)
+    }
+    fn image(&self) -> Option<Image> {
+        let cave = self.world.blocks.to_grid(|cell| match cell {
+            Some(Cell::Rock) => [128, 128, 128],
+            Some(Cell::Sand) => [230, 200, 100],
+            None => [0, 0, 0],
+        });
+        Some(cave.pixmap(|&color| color))
+    }
+}
+
+// Rock lines are filled cell by cell, and the floor spans the whole cave
+const MAX_COORD: i32 = 1000;
+
+fn parse_coord(word: &Token) -> Result<i32, ParseError> {
+    let expected = format!("a coordinate between 0 and {}", MAX_COORD);
+    match word.parse::<i32>(&expected)? {
+        c if (0..=MAX_COORD).contains(&c) => Ok(c),
+        _ => Err(word.error(&expected)),
+    }
+}
+
+pub fn parse(content: &str) -> Result<World, ParseError> {
+    let mut world = World::new();
+    for line in lines(content) {
+        let mut cur = None;
+        for content in line.split("->") {
+            let content = content.trim_matches(char::is_whitespace);
+            let coords = content
+                .split_n(",", 2, "a point like `498,4`")?
+                .iter()
+                .map(parse_coord)
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some((x0, y0)) = cur {
+                let (x1, y1) = (coords[0], coords[1]);
+                if x0 != x1 && y0 != y1 {
+                    return Err(content.error("a point in line with the previous one"));
+                }
+                world.add_rockline(x0, y0, x1, y1);
+            }
+            cur = Some((coords[0], coords[1]));
+            debug!("Content {:?}", coords);
+        }
+    }
+    Ok(world)
+}
+
+pub fn part1(world: &World) -> u32 {
+    let mut world = world.clone();
+    trace!("World:\n{}", world.draw());
+    explain!("Rocks down to y = {}, the abyss below", world.abyss_y - 1);
+    let count = world.drop_sand();
+    debug!("World:\n{}", world.draw());
+    count
+}
+
+pub fn part2(world: &World) -> u32 {
+    let mut world = world.clone();
+    explain!(
+        "Rocks down to y = {}, the floor at y = {}",
+        world.abyss_y - 1,
+        world.abyss_y + 1
+    );
+    world.add_floor();
+    trace!("World:\n{}", world.draw());
+    world.drop_sand()
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = World;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(content: &str) -> Result<World, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &World, _: &()) -> u32 {
+        part1(input)
+    }
+
+    fn part2(input: &World, _: &()) -> u32 {
+        part2(input)
+    }
+
+    fn simulation(input: &World, _: &(), part: u8) -> Option<Box<dyn Simulation>> {
+        let mut world = input.clone();
+        if part == 2 {
+            world.add_floor();
+        }
+        Some(Box::new(SandSimulation { world }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 24);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 93);
+    }
+}
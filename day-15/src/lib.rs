@@ -0,0 +1,236 @@
+use std::ops::RangeInclusive;
+
+use common::{explain, lines, ParseError, Token};
+use log::{debug, trace};
+
+#[derive(Debug, Clone)]
+pub struct SensorData {
+    pub s_x: i32,
+    pub s_y: i32,
+    pub b_x: i32,
+    pub b_y: i32,
+    pub dist: i32,
+}
+impl SensorData {
+    pub fn new(s_x: i32, s_y: i32, b_x: i32, b_y: i32) -> SensorData {
+        let dist = (s_x - b_x).abs() + (s_y - b_y).abs();
+        SensorData {
+            s_x,
+            s_y,
+            b_x,
+            b_y,
+            dist,
+        }
+    }
+    pub fn not_present(&self, y: i32, beacon_as_present: bool) -> Option<RangeInclusive<i32>> {
+        let y_dist = self.dist - (self.s_y - y).abs();
+        if y_dist > 0 {
+            let mut min_x = self.s_x - y_dist;
+            if beacon_as_present && y == self.b_y && min_x == self.b_x {
+                min_x += 1;
+            }
+            let mut max_x = self.s_x + y_dist;
+            if beacon_as_present && y == self.b_y && max_x == self.b_x {
+                max_x -= 1;
+            }
+            Some(min_x..=max_x)
+        } else {
+            None
+        }
+    }
+}
+// Distances and ranges are computed on i32
+const MAX_COORD: i32 = 100_000_000;
+
+// `x=2,` -> 2
+fn parse_coord(word: Token, prefix: &str, suffix: &str) -> Result<i32, ParseError> {
+    let number = word.strip_prefix(prefix)?.strip_suffix(suffix)?;
+    let expected = format!("a coordinate between -{0} and {0}", MAX_COORD);
+    match number.parse::<i32>(&expected)? {
+        c if (-MAX_COORD..=MAX_COORD).contains(&c) => Ok(c),
+        _ => Err(number.error(&expected)),
+    }
+}
+// The example uses a different row and search area than the real inputs,
+// set in day-15/example.toml
+common::params! {
+    pub struct Params {
+        /// Row where part 1 counts the positions without beacon
+        pub row: i32 = 2_000_000,
+        /// Part 2 searches the beacon from 0 to max, in x and y
+        pub max: i32 = 4_000_000,
+    }
+}
+
+pub fn parse(content: &str) -> Result<Vec<SensorData>, ParseError> {
+    let mut sensors = vec![];
+    for line in lines(content) {
+        // Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+        for (i, word) in [
+            (0, "Sensor"),
+            (1, "at"),
+            (4, "closest"),
+            (5, "beacon"),
+            (6, "is"),
+            (7, "at"),
+        ] {
+            line.word(i, &format!("`{}`", word))?.expect(word)?;
+        }
+        let s_x = parse_coord(line.word(2, "`x=`")?, "x=", ",")?;
+        let s_y = parse_coord(line.word(3, "`y=`")?, "y=", ":")?;
+        let b_x = parse_coord(line.word(8, "`x=`")?, "x=", ",")?;
+        let b_y = parse_coord(line.word(9, "`y=`")?, "y=", "")?;
+        sensors.push(SensorData::new(s_x, s_y, b_x, b_y));
+    }
+    Ok(sensors)
+}
+
+pub fn part1(sensors: &[SensorData], params: &Params) -> u32 {
+    let (ranges, min_x, max_x) = not_present(sensors, params.row, true);
+    let mut count = 0u32;
+    for x in min_x..=max_x {
+        for range in ranges.iter() {
+            if range.contains(&x) {
+                count += 1;
+                break;
+            }
+        }
+    }
+    debug!("Ranges: {:?}", ranges);
+    explain!(
+        "Row {}: reached by {} of {} sensors, without beacon on {:?}: {} positions",
+        params.row,
+        sensors
+            .iter()
+            .filter(|s| s.not_present(params.row, false).is_some())
+            .count(),
+        sensors.len(),
+        ranges,
+        count
+    );
+    count
+}
+
+pub fn part2(sensors: &[SensorData], params: &Params) -> u64 {
+    let max = params.max;
+    let mut possibilities = vec![];
+    for y in 0..=max {
+        let (ranges, _, _) = not_present(sensors, y, false);
+        trace!("Y: {}   => {:?}", y, ranges);
+        for range in ranges.iter() {
+            if range.start() > &0 && range.start() < &max {
+                possibilities.push((range.start() - 1, y));
+                explain!("Row {}: not covered left of {:?}", y, range);
+                break;
+            }
+            if range.end() > &0 && range.end() < &max {
+                possibilities.push((range.end() + 1, y));
+                explain!("Row {}: not covered right of {:?}", y, range);
+                break;
+            }
+        }
+    }
+    assert!(possibilities.len() == 1, "More than one possibility found");
+    // Tuning frequency
+    let (x, y) = possibilities[0];
+    let frequency = x as u64 * 4000000 + y as u64;
+    explain!(
+        "Beacon at ({}, {}): {} * 4000000 + {} = {}",
+        x,
+        y,
+        x,
+        y,
+        frequency
+    );
+    frequency
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<SensorData>;
+    type Params = Params;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(content: &str) -> Result<Vec<SensorData>, ParseError> {
+        parse(content)
+    }
+
+    fn check(_: &Vec<SensorData>, params: &Params) -> Result<(), String> {
+        if params.row.abs() > MAX_COORD || !(0..=MAX_COORD).contains(&params.max) {
+            return Err(format!(
+                "row must be between -{0} and {0}, and max between 0 and {0}",
+                MAX_COORD
+            ));
+        }
+        Ok(())
+    }
+
+    fn part1(input: &Vec<SensorData>, params: &Params) -> u32 {
+        part1(input, params)
+    }
+
+    fn part2(input: &Vec<SensorData>, params: &Params) -> u64 {
+        part2(input, params)
+    }
+}
+
+fn not_present(
+    sensors: &[SensorData],
+    y: i32,
+    beacons_as_present: bool,
+) -> (Vec<RangeInclusive<i32>>, i32, i32) {
+    let mut min_x = i32::MAX;
+    let mut max_x = i32::MIN;
+    let mut ranges = vec![];
+    for sensor in sensors {
+        if let Some(range) = sensor.not_present(y, beacons_as_present) {
+            min_x = min_x.min(*range.start());
+            max_x = max_x.max(*range.end());
+            ranges.push(range);
+        }
+    }
+    ranges.sort_by(|r0, r1| r0.start().cmp(r1.start()));
+    loop {
+        let mut new = None;
+        let mut rem_idx = 0;
+        for (i, range) in ranges.iter().enumerate() {
+            if i == 0 {
+                continue;
+            }
+            if *(ranges[i - 1].end()) + 1 >= *range.start() {
+                let start = ranges[i - 1].start();
+                let end = range.end().max(ranges[i - 1].end());
+                new = Some(*start..=*end);
+                rem_idx = i;
+                break;
+            }
+        }
+        if let Some(range) = new {
+            ranges[rem_idx - 1] = range;
+            ranges.remove(rem_idx);
+        } else {
+            break;
+        }
+    }
+    (ranges, min_x, max_x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE_PARAMS: Params = Params { row: 10, max: 20 };
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap(), &EXAMPLE_PARAMS), 26);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap(), &EXAMPLE_PARAMS), 56000011);
+    }
+}
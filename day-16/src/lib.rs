@@ -0,0 +1,427 @@
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+
+use common::{checkpoint::Record, counters, explain, lines, ParseError};
+use log::{info, trace};
+use search::Maximize;
+
+#[derive(Debug, Clone)]
+pub struct Valve {
+    pub flow: u32,
+    pub tunnels: Vec<u32>,
+}
+
+#[derive(Debug, Clone)]
+enum Action {
+    GoTo(u32),
+    OpenValve(u32),
+}
+
+#[derive(Debug, Clone)]
+struct Path {
+    opened_valves: Vec<u32>,
+    position: Vec<u32>,
+    score: u32,
+    min_score: u32,
+    // Positions since the last valve opened, not worth coming back to
+    visited: Vec<Vec<u32>>,
+    time: u32,
+    end_time: u32,
+}
+impl Path {
+    fn new(start_id: u32, count: usize, end_time: u32) -> Path {
+        Path {
+            opened_valves: vec![],
+            position: vec![start_id; count],
+            score: 0,
+            min_score: 0,
+            visited: vec![],
+            time: 0,
+            end_time,
+        }
+    }
+    fn update(&mut self, valves: &HashMap<u32, Valve>) {
+        self.time += 1;
+        let tick_score = self
+            .opened_valves
+            .iter()
+            .map(|id| valves[id].flow)
+            .sum::<u32>();
+        self.score += tick_score;
+        self.min_score = self.score + tick_score * (self.end_time - self.time);
+        if !self.visited.contains(&self.position) {
+            self.visited.push(self.position.clone());
+        }
+    }
+    fn next(&self, valves: &HashMap<u32, Valve>) -> Vec<Path> {
+        let mut pathes = vec![];
+        let mut actions = vec![vec![]];
+        if self.position.len() == 2 && self.position[0] == self.position[1] {
+            let mut new_actions = valves[&self.position[0]]
+                .tunnels
+                .iter()
+                .map(|v| Action::GoTo(*v))
+                .collect::<Vec<_>>();
+            new_actions.push(Action::OpenValve(self.position[0]));
+            actions = vec![];
+            for (i, action0) in new_actions.iter().enumerate() {
+                for action1 in new_actions.iter().skip(i) {
+                    actions.push(vec![action0.clone(), action1.clone()])
+                }
+            }
+        } else {
+            for position in self.position.iter() {
+                let mut new_actions = vec![];
+                let mut my_actions = valves[position]
+                    .tunnels
+                    .iter()
+                    .map(|v| Action::GoTo(*v))
+                    .collect::<Vec<_>>();
+                my_actions.push(Action::OpenValve(*position));
+                for action in actions.iter() {
+                    for my_action in my_actions.iter() {
+                        let mut new_action = action.clone();
+                        new_action.push(my_action.clone());
+                        new_actions.push(new_action);
+                    }
+                }
+                actions = new_actions;
+            }
+        }
+        for action in actions.iter() {
+            let mut new_path = self.clone();
+            let mut new_position = self.position.clone();
+            let mut ok = true;
+            for (i, my_action) in action.iter().enumerate() {
+                match my_action {
+                    Action::GoTo(id) => {
+                        new_position[i] = *id;
+                    }
+                    Action::OpenValve(id) => {
+                        if valves[id].flow > 0 && !new_path.opened_valves.contains(id) {
+                            new_path.opened_valves.push(*id);
+                            new_path.opened_valves.sort();
+                            new_path.visited.clear();
+                        } else {
+                            ok = false;
+                        }
+                    }
+                }
+            }
+            new_position.sort();
+            if ok && (new_position == self.position || !self.visited.contains(&new_position)) {
+                new_path.position = new_position;
+                pathes.push(new_path);
+            }
+        }
+        pathes
+    }
+}
+
+impl Record for Path {
+    fn record(&self, numbers: &mut Vec<u64>) {
+        self.opened_valves.record(numbers);
+        self.position.record(numbers);
+        (self.score, self.min_score, self.time).record(numbers);
+        self.end_time.record(numbers);
+        self.visited.record(numbers);
+    }
+    fn restore(numbers: &mut impl Iterator<Item = u64>) -> Option<Path> {
+        let opened_valves = Record::restore(numbers)?;
+        let position = Record::restore(numbers)?;
+        let (score, min_score, time) = Record::restore(numbers)?;
+        Some(Path {
+            opened_valves,
+            position,
+            score,
+            min_score,
+            time,
+            end_time: Record::restore(numbers)?,
+            visited: Record::restore(numbers)?,
+        })
+    }
+}
+
+struct ValveIds {
+    ids: HashMap<String, u32>,
+    cur_id: u32,
+}
+impl ValveIds {
+    fn new() -> ValveIds {
+        ValveIds {
+            ids: HashMap::new(),
+            cur_id: 0,
+        }
+    }
+    fn get(&mut self, name: &str) -> u32 {
+        let name = name.to_string();
+        match self.ids.entry(name) {
+            Entry::Vacant(e) => {
+                let id = self.cur_id;
+                e.insert(self.cur_id);
+                self.cur_id += 1;
+                id
+            }
+            Entry::Occupied(e) => *e.get(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Volcano {
+    pub valves: HashMap<u32, Valve>,
+    /// Valve ids by name
+    pub ids: HashMap<String, u32>,
+}
+impl Volcano {
+    /// Minutes to walk from each valve to valve `to`, by valve id, u32::MAX
+    /// when out of reach.
+    fn distances(&self, to: u32) -> Vec<u32> {
+        let size = self.valves.keys().max().map_or(0, |&id| id as usize + 1);
+        let mut distances = vec![u32::MAX; size];
+        distances[to as usize] = 0;
+        let mut queue = VecDeque::from([to]);
+        while let Some(id) = queue.pop_front() {
+            for &tunnel in self.valves[&id].tunnels.iter() {
+                if distances[tunnel as usize] == u32::MAX {
+                    distances[tunnel as usize] = distances[id as usize] + 1;
+                    queue.push_back(tunnel);
+                }
+            }
+        }
+        distances
+    }
+    pub fn best_score(&self, start: &str, end_time: u32, n_actor: usize) -> u32 {
+        let mut flows = self
+            .valves
+            .iter()
+            .filter(|(_, v)| v.flow > 0)
+            .map(|(id, v)| (v.flow, *id))
+            .collect::<Vec<_>>();
+        flows.sort_unstable_by(|a, b| b.cmp(a));
+        let distances = flows.iter().map(|&(_, id)| self.distances(id)).collect();
+        let start_id = *self
+            .ids
+            .get(start)
+            .unwrap_or_else(|| panic!("No valve {} to start from", start));
+        let mut start = Path::new(start_id, n_actor, end_time);
+        start.update(&self.valves);
+        let pressure = Pressure {
+            valves: &self.valves,
+            flows,
+            distances,
+        };
+        let (best, stats) = search::branch_and_bound(&pressure, start);
+        info!("Iterations: {}", stats.iterations);
+        info!("Useless skip: {}", stats.pruned);
+        info!("Cache size: {}", stats.seen);
+        counters::set("iterations", stats.iterations);
+        counters::set("useless_count", stats.pruned);
+        counters::set("cache_size", stats.seen);
+        if let Some(abort) = stats.aborted {
+            info!("Stopped by {}, best so far", abort);
+        }
+        explain!(
+            "{} paths explored, {} cut by their bound",
+            stats.iterations,
+            stats.pruned
+        );
+        if let Some((score, path)) = &best {
+            let names = self
+                .ids
+                .iter()
+                .map(|(name, id)| (*id, name.as_str()))
+                .collect::<HashMap<_, _>>();
+            let opened = path
+                .opened_valves
+                .iter()
+                .map(|id| format!("{} ( {} )", names[id], self.valves[id].flow))
+                .collect::<Vec<_>>();
+            explain!(
+                "Best: {} released in {} minutes, opening {} of {} valves: {}",
+                score,
+                end_time,
+                opened.len(),
+                self.valves.values().filter(|v| v.flow > 0).count(),
+                opened.join(", ")
+            );
+        }
+        best.map_or(0, |(score, _)| score)
+    }
+}
+
+// Pressure released by the end time, paths are updated up to their time
+struct Pressure<'a> {
+    valves: &'a HashMap<u32, Valve>,
+    // (flow, id) of the valves worth opening, largest flow first
+    flows: Vec<(u32, u32)>,
+    // Minutes from each valve to the valves of flows, by valve id
+    distances: Vec<Vec<u32>>,
+}
+
+impl Maximize for Pressure<'_> {
+    type State = Path;
+    type Score = u32;
+    type Key = (Vec<u32>, Vec<u32>);
+    // (time, score)
+    type Mark = (u32, u32);
+    const BEST_FIRST: bool = true;
+
+    fn successors(&self, path: &Path, next: &mut Vec<Path>) {
+        if path.time == path.end_time {
+            return;
+        }
+        trace!("Cur: {:?}", path);
+        for mut path in path.next(self.valves) {
+            path.update(self.valves);
+            next.push(path);
+        }
+    }
+    fn score(&self, path: &Path) -> u32 {
+        path.min_score
+    }
+    // The least of two bounds: each actor opens at most one closed valve every
+    // 2 minutes ( a move and the opening ), the largest first, and no closed
+    // valve flows before the nearest actor walks to it
+    fn upper_bound(&self, path: &Path) -> u32 {
+        let left = path.end_time - path.time;
+        let actors = path.position.len();
+        let (mut by_turns, mut by_distance) = (0, 0);
+        for (i, (&(flow, _), distances)) in self
+            .flows
+            .iter()
+            .zip(self.distances.iter())
+            .filter(|((_, id), _)| !path.opened_valves.contains(id))
+            .enumerate()
+        {
+            by_turns += flow * left.saturating_sub(2 * (i / actors) as u32);
+            let distance = path.position.iter().map(|&p| distances[p as usize]).min();
+            by_distance += flow * left.saturating_sub(distance.unwrap_or(left));
+        }
+        path.min_score + by_turns.min(by_distance)
+    }
+    fn priority(&self, path: &Path) -> u64 {
+        path.min_score as u64
+    }
+    fn dedup(&self, path: &Path) -> Option<(Self::Key, Self::Mark)> {
+        let key = (path.opened_valves.clone(), path.position.clone());
+        Some((key, (path.time, path.score)))
+    }
+    // Same valves opened at the same place, earlier and with more pressure
+    fn dominates(&self, seen: &(u32, u32), mark: &(u32, u32)) -> bool {
+        seen.0 <= mark.0 && seen.1 >= mark.1
+    }
+}
+
+pub fn parse(content: &str) -> Result<Volcano, ParseError> {
+    let mut valves = HashMap::new();
+    let mut valve_ids = ValveIds::new();
+    let mut all_tunnels = vec![];
+
+    for line in lines(content) {
+        // Valve BB has flow rate=13; tunnels lead to valves CC, AA
+        for (i, word) in [(0, "Valve"), (2, "has"), (3, "flow")] {
+            line.word(i, &format!("`{}`", word))?.expect(word)?;
+        }
+        let name = line.word(1, "a valve name")?;
+        let id = valve_ids.get(name.text);
+        if valves.contains_key(&id) {
+            return Err(name.error("a valve not already described"));
+        }
+        let flow = line
+            .word(4, "`rate=N;`")?
+            .strip_prefix("rate=")?
+            .strip_suffix(";")?
+            .parse::<u32>("a flow rate")?;
+        line.word(9, "a list of valves")?;
+        let tunnels = line
+            .words()
+            .into_iter()
+            .skip(9)
+            .map(|s| s.trim_end_matches(','))
+            .collect::<Vec<_>>();
+        all_tunnels.extend(tunnels.iter().copied());
+        let tunnels = tunnels
+            .iter()
+            .map(|valve_name| valve_ids.get(valve_name.text))
+            .collect::<Vec<_>>();
+        valves.insert(id, Valve { flow, tunnels });
+    }
+    if let Some(unknown) = all_tunnels
+        .iter()
+        .find(|t| !valves.contains_key(&valve_ids.get(t.text)))
+    {
+        return Err(unknown.error("a described valve"));
+    }
+    info!("Loaded {} valves", valves.len());
+    Ok(Volcano {
+        valves,
+        ids: valve_ids.ids,
+    })
+}
+
+common::params! {
+    pub struct Params {
+        /// Minutes before the eruption, alone
+        pub minutes1: u32 = 30,
+        /// Minutes left with the elephant
+        pub minutes2: u32 = 26,
+        /// Valve where everyone starts
+        pub start: String = String::from("AA"),
+    }
+}
+
+pub fn part1(volcano: &Volcano, params: &Params) -> u32 {
+    volcano.best_score(&params.start, params.minutes1, 1)
+}
+
+pub fn part2(volcano: &Volcano, params: &Params) -> u32 {
+    volcano.best_score(&params.start, params.minutes2, 2)
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Volcano;
+    type Params = Params;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(content: &str) -> Result<Volcano, ParseError> {
+        parse(content)
+    }
+
+    fn check(volcano: &Volcano, params: &Params) -> Result<(), String> {
+        if !volcano.ids.contains_key(&params.start) {
+            return Err(format!("no valve {} to start from", params.start));
+        }
+        if params.minutes1 == 0 || params.minutes2 == 0 {
+            return Err(String::from("minutes must be at least 1"));
+        }
+        Ok(())
+    }
+
+    fn part1(input: &Volcano, params: &Params) -> u32 {
+        part1(input, params)
+    }
+
+    fn part2(input: &Volcano, params: &Params) -> u32 {
+        part2(input, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap(), &Params::default()), 1651);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap(), &Params::default()), 1707);
+    }
+}
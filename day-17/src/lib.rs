@@ -0,0 +1,384 @@
+use std::num::NonZeroUsize;
+
+use common::{counters, explain, lines, Image, ParseError, Simulation};
+use grid::Grid;
+use log::{debug, info, log_enabled, trace, Level};
+use lru::LruCache;
+
+// Rows are kept as bit masks for speed, the grid is only used to draw them,
+// top row first.
+fn rows_grid(rows: &[u8]) -> Grid<bool> {
+    Grid::from_rows(
+        rows.iter()
+            .rev()
+            .map(|row| (0..World::WIDTH).map(|i| (row >> i) % 2 != 0).collect())
+            .collect(),
+    )
+    .unwrap()
+}
+fn draw_rows(rows: &[u8]) -> String {
+    rows_grid(rows).render(|&filled| if filled { '#' } else { '.' })
+}
+
+#[derive(Clone)]
+struct Rock {
+    rows: Vec<u8>,
+}
+impl Rock {
+    fn new(rows: Vec<u8>) -> Rock {
+        Rock { rows }
+    }
+    fn new_with_step(rock: &Rock, step: u8) -> Rock {
+        Rock {
+            rows: rock.rows.iter().map(|row| row << step).collect::<Vec<_>>(),
+        }
+    }
+    fn draw(&self) -> String {
+        draw_rows(&self.rows)
+    }
+    fn with_wind(&mut self, wind: i8) -> Rock {
+        match wind {
+            1 => {
+                if self.rows.iter().all(|row| (row >> (World::WIDTH - 1)) == 0) {
+                    Rock {
+                        rows: self.rows.iter().map(|row| row << 1).collect::<Vec<_>>(),
+                    }
+                } else {
+                    self.clone()
+                }
+            }
+            _ => {
+                if self.rows.iter().all(|row| row % 2 == 0) {
+                    Rock {
+                        rows: self.rows.iter().map(|row| row >> 1).collect::<Vec<_>>(),
+                    }
+                } else {
+                    self.clone()
+                }
+            }
+        }
+    }
+}
+struct World {
+    winds: Vec<i8>,
+    wind_id: usize,
+    rocks: Vec<Rock>,
+    base_height: i64,
+    rock_id: usize,
+    rock_nb: u32,
+    // true if filled, false if empty
+    state: Vec<u8>,
+    cache: LruCache<(usize, usize, Vec<u8>), (u64, i64)>,
+}
+
+impl World {
+    const WIDTH: i32 = 7;
+    fn new(winds: &[i8]) -> World {
+        let rocks = vec![
+            Rock::new(vec![15]),
+            Rock::new(vec![2, 7, 2]),
+            Rock::new(vec![7, 4, 4]),
+            Rock::new(vec![1, 1, 1, 1]),
+            Rock::new(vec![3, 3]),
+        ];
+
+        World {
+            winds: winds.to_vec(),
+            wind_id: 0,
+            rocks,
+            rock_id: 0,
+            rock_nb: 0,
+            base_height: 0,
+            state: vec![],
+            cache: LruCache::new(NonZeroUsize::new(5_000_000).unwrap()),
+        }
+    }
+    fn draw(&self) -> String {
+        let mut s = String::new();
+        for row in draw_rows(&self.state).lines() {
+            s += &format!("|{}|\n", row);
+        }
+        s += &format!(
+            "+{}+\n",
+            vec!['-'; World::WIDTH as usize].iter().collect::<String>()
+        );
+        s += &format!("+ {} \n\n", self.base_height);
+        s += &format!("Dropped: {}", self.rock_nb);
+        s
+    }
+    fn height(&self) -> i64 {
+        self.base_height + self.state.len() as i64
+    }
+    fn set_height(&mut self, height: i64) {
+        assert!(height >= 0);
+        for _ in 0..(height - self.height()) {
+            self.state.push(0u8);
+        }
+    }
+    fn collides(&self, y: i64, row: &u8) -> bool {
+        y < self.base_height
+            || (y < self.height() && (self.state[(y - self.base_height) as usize] & row) != 0)
+    }
+    fn simplify(&mut self, iteration_idx: u64) -> Option<(u64, i64)> {
+        let mut scan = 0u8;
+        let full = (1u8 << World::WIDTH) - 1;
+        let mut i = self.state.len() - 1;
+        loop {
+            scan |= self.state[i];
+            if scan == full || i == 0 {
+                break;
+            }
+            i -= 1;
+        }
+        if scan == full {
+            self.state = self.state[i..self.state.len()].to_vec();
+            self.base_height += i as i64;
+        }
+        let entry = (self.wind_id, self.rock_id, self.state.clone());
+        if self.cache.contains(&entry) {
+            self.cache.get(&entry).copied()
+        } else {
+            self.cache.put(entry, (iteration_idx, self.base_height));
+            None
+        }
+    }
+    fn drop_one(&mut self, iteration_idx: u64, debug: bool) -> Option<(u64, i64)> {
+        if debug {
+            trace!("Rock choice\n{}", self.rocks[self.rock_id].draw());
+        }
+        let mut rock = Rock::new_with_step(&self.rocks[self.rock_id], 2);
+        let mut rock_base = self.height() + 3;
+        self.rock_id = (self.rock_id + 1) % self.rocks.len();
+        loop {
+            if debug {
+                trace!("Rock base: {}\n{}", rock_base, rock.draw());
+            }
+            // Apply winds
+            let wind = self.winds[self.wind_id];
+            self.wind_id = (self.wind_id + 1) % self.winds.len();
+            let new_rock = rock.with_wind(wind);
+            let mut ok = true;
+            for (i, rock_row) in new_rock.rows.iter().enumerate() {
+                if self.collides(i as i64 + rock_base, rock_row) {
+                    ok = false;
+                    break;
+                }
+            }
+            if ok {
+                rock = new_rock;
+            }
+            if debug {
+                trace!("Wind:{:2}", wind);
+                trace!("Rock base: {}\n{}", rock_base, rock.draw());
+            }
+            // Apply fall
+            let mut ok = true;
+            for (i, rock_row) in rock.rows.iter().enumerate() {
+                if self.collides(i as i64 + rock_base - 1, rock_row) {
+                    ok = false;
+                    break;
+                }
+            }
+            if !ok {
+                break;
+            }
+            rock_base -= 1;
+        }
+        if debug {
+            trace!("Rock end. base: {}\n{}", rock_base, rock.draw());
+        }
+        self.set_height(rock_base + rock.rows.len() as i64);
+        rock.rows.iter().enumerate().for_each(|(i, row)| {
+            let y = i + (rock_base - self.base_height) as usize;
+            assert!(
+                (self.state[y] & row) == 0,
+                "Missed: {} vs {}",
+                self.state[y],
+                row
+            );
+            self.state[y] |= row;
+        });
+
+        self.rock_nb += 1;
+        self.simplify(iteration_idx)
+    }
+}
+fn tower_height(wind: &[i8], n_iteration: u64) -> i64 {
+    let mut world = World::new(wind);
+
+    let mut i = 0;
+    let mut jumped = false;
+    while i < n_iteration {
+        if i % 1_000_000 == 0 {
+            debug!("I: {}M Height: {}", i / 1_000_000, world.height());
+        }
+        match world.drop_one(i, log_enabled!(Level::Trace)) {
+            Some((prev_i, prev_base_height)) if !jumped => {
+                let i_step = i - prev_i;
+                let height_step = world.base_height - prev_base_height;
+                let n_loop = (n_iteration - (i + 1)) / i_step;
+                info!(
+                    "At I {} found {} loops of step {} -> {}",
+                    i,
+                    n_loop,
+                    i_step,
+                    i + i_step * n_loop + 1
+                );
+                counters::set("loop_start", prev_i);
+                counters::set("loop_step", i_step);
+                counters::set("loop_height", height_step as u64);
+                counters::set("loop_count", n_loop);
+                explain!(
+                    "Rock {}: same top, jet and next rock as after rock {}, {} rocks raise the tower by {}",
+                    i + 1,
+                    prev_i + 1,
+                    i_step,
+                    height_step
+                );
+                explain!(
+                    "Repeated {} times: {} * {} = {} more rows, up to rock {}",
+                    n_loop,
+                    n_loop,
+                    height_step,
+                    height_step * n_loop as i64,
+                    i + i_step * n_loop + 1
+                );
+                world.base_height += height_step * n_loop as i64;
+                i += i_step * n_loop + 1;
+                jumped = true;
+            }
+            _ => {
+                i += 1;
+            }
+        }
+    }
+    info!("Base_height: {}", world.base_height);
+
+    trace!("Top of the tower:\n{}", world.draw());
+    explain!("Height after {} rocks: {}", n_iteration, world.height());
+    world.height()
+}
+
+/// The rocks falling one at a time, the top of the tower shown down to its
+/// last full row.
+pub struct TowerSimulation {
+    world: World,
+    n_rock: u64,
+}
+
+impl Simulation for TowerSimulation {
+    fn step(&mut self) -> bool {
+        let dropped = self.world.rock_nb as u64;
+        if dropped >= self.n_rock {
+            return false;
+        }
+        self.world.drop_one(dropped, false);
+        true
+    }
+    fn draw(&self) -> String {
+        self.world.draw()
+    }
+    fn status(&self) -> String {
+        format!(
+            "Rocks: {}/{}  Height: {}",
+            self.world.rock_nb,
+            self.n_rock,
+            self.world.height()
+        )
+    }
+    fn image(&self) -> Option<Image> {
+        Some(rows_grid(&self.world.state).bitmap(|&filled| filled))
+    }
+}
+
+pub fn parse(content: &str) -> Result<Vec<i8>, ParseError> {
+    let mut lines = lines(content);
+    let Some(line) = lines.next() else {
+        return Err(ParseError::new(1, 1, "a jet pattern", ""));
+    };
+    if let Some(extra) = lines.find(|l| !l.is_empty()) {
+        return Err(extra.error("a single line"));
+    }
+    line.chars()
+        .map(|c| match c.char() {
+            '<' => Ok(-1),
+            '>' => Ok(1),
+            _ => Err(c.error("`<` or `>`")),
+        })
+        .collect::<Result<Vec<_>, _>>()
+}
+
+common::params! {
+    pub struct Params {
+        /// Rocks fallen for part 1
+        pub rocks1: u64 = 2022,
+        /// Rocks fallen for part 2
+        pub rocks2: u64 = 1_000_000_000_000,
+    }
+}
+
+pub fn part1(wind: &[i8], params: &Params) -> i64 {
+    tower_height(wind, params.rocks1)
+}
+
+pub fn part2(wind: &[i8], params: &Params) -> i64 {
+    tower_height(wind, params.rocks2)
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<i8>;
+    type Params = Params;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(content: &str) -> Result<Vec<i8>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Vec<i8>, params: &Params) -> i64 {
+        part1(input, params)
+    }
+
+    fn part2(input: &Vec<i8>, params: &Params) -> i64 {
+        part2(input, params)
+    }
+
+    fn simulation(input: &Vec<i8>, params: &Params, part: u8) -> Option<Box<dyn Simulation>> {
+        let n_rock = if part == 1 {
+            params.rocks1
+        } else {
+            params.rocks2
+        };
+        Some(Box::new(TowerSimulation {
+            world: World::new(input),
+            n_rock,
+        }))
+    }
+}
+// Example 2 10M : 28s -> 15142861
+// Example 2 100M release mode: 12s -> 151428577
+// Input 2 10M : 53s -> 15371762
+// Example 1 3068
+// Input 1 3106
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap(), &Params::default()), 3068);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            part2(&parse(EXAMPLE).unwrap(), &Params::default()),
+            1514285714288
+        );
+    }
+}
@@ -0,0 +1,210 @@
+#![allow(clippy::needless_range_loop)]
+use std::collections::HashSet;
+
+use common::{explain, lines, ParseError, Token};
+use log::{debug, info, trace};
+
+pub struct World {
+    pub cubes: HashSet<Vec<i32>>,
+    pub external_air: HashSet<Vec<i32>>,
+    pub min: Vec<i32>,
+    pub max: Vec<i32>,
+}
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl World {
+    pub fn new() -> World {
+        World {
+            cubes: HashSet::new(),
+            external_air: HashSet::new(),
+            min: vec![i32::MAX, i32::MAX, i32::MAX],
+            max: vec![i32::MIN, i32::MIN, i32::MIN],
+        }
+    }
+    pub fn add_cube(&mut self, pos: &[i32]) {
+        assert!(pos.len() == 3);
+        self.cubes.insert(pos.to_vec());
+        for i in 0..3 {
+            self.max[i] = self.max[i].max(pos[i]);
+            self.min[i] = self.min[i].min(pos[i]);
+        }
+    }
+    pub fn build_external_air(&mut self) {
+        assert!(self.external_air.is_empty());
+        debug!("BUILDING");
+        self.external_air = self.get_connected_air(vec![0, 0, 0]);
+    }
+    pub fn get_connected_air(&self, pos: Vec<i32>) -> HashSet<Vec<i32>> {
+        let mut connected_air = HashSet::new();
+        let mut to_check = vec![pos];
+        while let Some(cur) = to_check.pop() {
+            trace!("CUR {:?}", cur);
+            if self.cubes.contains(&cur) || connected_air.contains(&cur) {
+                continue;
+            }
+            connected_air.insert(cur.to_vec());
+            for dpos in [
+                vec![-1, 0, 0],
+                vec![1, 0, 0],
+                vec![0, -1, 0],
+                vec![0, 1, 0],
+                vec![0, 0, -1],
+                vec![0, 0, 1],
+            ] {
+                let new_pos = vec![cur[0] + dpos[0], cur[1] + dpos[1], cur[2] + dpos[2]];
+                if (0..3).any(|i| new_pos[i] < self.min[i] - 1 || new_pos[i] > self.max[i] + 1) {
+                    continue;
+                }
+                to_check.push(new_pos);
+            }
+        }
+        connected_air
+    }
+}
+impl World {
+    pub fn surface(&self, external_only: bool) -> u32 {
+        let mut n_surface = 0;
+        for cube in self.cubes.iter() {
+            for (dx, dy, dz) in [
+                (-1, 0, 0),
+                (1, 0, 0),
+                (0, -1, 0),
+                (0, 1, 0),
+                (0, 0, 1),
+                (0, 0, -1),
+            ] {
+                let side_coords = vec![cube[0] + dx, cube[1] + dy, cube[2] + dz];
+                if !self.cubes.contains(&side_coords) {
+                    if !external_only {
+                        n_surface += 1;
+                    } else {
+                        if self.external_air.contains(&side_coords) {
+                            n_surface += 1;
+                        }
+                    }
+                }
+            }
+        }
+        n_surface
+    }
+}
+
+// The external air is flood filled over the whole bounding box
+const MAX_COORD: i32 = 50;
+
+fn parse_coord(word: &Token) -> Result<i32, ParseError> {
+    let expected = format!("a coordinate between 0 and {}", MAX_COORD);
+    match word.parse::<i32>(&expected)? {
+        c if (0..=MAX_COORD).contains(&c) => Ok(c),
+        _ => Err(word.error(&expected)),
+    }
+}
+
+pub fn parse(content: &str) -> Result<World, ParseError> {
+    let mut world = World::new();
+    for cube in lines(content) {
+        let coords = cube
+            .split_n(",", 3, "a cube like `2,2,2`")?
+            .iter()
+            .map(parse_coord)
+            .collect::<Result<Vec<_>, _>>()?;
+        world.add_cube(&coords)
+    }
+    info!("N cubes: {}", world.cubes.len());
+    debug!("Mins: {:?}", world.min);
+    debug!("Maxs: {:?}", world.max);
+    world.build_external_air();
+    Ok(world)
+}
+
+pub fn part1(world: &World) -> u32 {
+    let surface = world.surface(false);
+    explain!(
+        "{} cubes: 6 * {} faces - {} against another cube = {}",
+        world.cubes.len(),
+        world.cubes.len(),
+        6 * world.cubes.len() as u32 - surface,
+        surface
+    );
+    surface
+}
+
+pub fn part2(world: &World) -> u32 {
+    let (all, surface) = (world.surface(false), world.surface(true));
+    explain!(
+        "Air flood filled from {:?} to {:?}: {} cubes outside",
+        world.min.iter().map(|c| c - 1).collect::<Vec<_>>(),
+        world.max.iter().map(|c| c + 1).collect::<Vec<_>>(),
+        world.external_air.len()
+    );
+    explain!(
+        "{} faces - {} against trapped air = {}",
+        all,
+        all - surface,
+        surface
+    );
+    surface
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = World;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(content: &str) -> Result<World, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &World, _: &()) -> u32 {
+        part1(input)
+    }
+
+    fn part2(input: &World, _: &()) -> u32 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE_A: &str = include_str!("../exampleA.txt");
+    const EXAMPLE_B: &str = include_str!("../exampleB.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 64);
+    }
+
+    #[test]
+    fn example_a_part1() {
+        assert_eq!(part1(&parse(EXAMPLE_A).unwrap()), 24);
+    }
+
+    #[test]
+    fn example_b_part1() {
+        assert_eq!(part1(&parse(EXAMPLE_B).unwrap()), 84);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 58);
+    }
+
+    #[test]
+    fn example_a_part2() {
+        assert_eq!(part2(&parse(EXAMPLE_A).unwrap()), 24);
+    }
+
+    #[test]
+    fn example_b_part2() {
+        assert_eq!(part2(&parse(EXAMPLE_B).unwrap()), 78);
+    }
+}
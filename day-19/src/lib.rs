@@ -0,0 +1,343 @@
+use common::{checkpoint::Record, explain, lines, ParseError, Token};
+use log::info;
+use search::Maximize;
+
+#[derive(Clone, Debug)]
+pub struct Blueprint {
+    pub id: u32,
+    pub ore_robot: u32,
+    pub clay_robot: u32,
+    pub obsidian_robot: (u32, u32),
+    pub geode_robot: (u32, u32),
+}
+impl Blueprint {
+    pub fn parse(line: Token) -> Result<Blueprint, ParseError> {
+        // Numbers are the `N` of the template
+        const TEMPLATE: &str = "Blueprint N: Each ore robot costs N ore. \
+            Each clay robot costs N ore. \
+            Each obsidian robot costs N ore and N clay. \
+            Each geode robot costs N ore and N obsidian.";
+        let words = line.words();
+        let mut numbers = vec![];
+        for (i, expected) in TEMPLATE.split_whitespace().enumerate() {
+            let word = line.word(i, &format!("`{}`", expected))?;
+            match expected.strip_prefix('N') {
+                Some(suffix) => {
+                    numbers.push(word.strip_suffix(suffix)?.parse::<u32>("a number")?);
+                }
+                None => word.expect(expected)?,
+            }
+        }
+        if let Some(extra) = words.get(TEMPLATE.split_whitespace().count()) {
+            return Err(extra.error("end of line"));
+        }
+        let id = numbers[0];
+        let ore_robot = numbers[1];
+        let clay_robot = numbers[2];
+        let obsidian_robot = (numbers[3], numbers[4]);
+        let geode_robot = (numbers[5], numbers[6]);
+        Ok(Blueprint {
+            id,
+            ore_robot,
+            clay_robot,
+            obsidian_robot,
+            geode_robot,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Run {
+    time: u32,
+    ore: u32,
+    clay: u32,
+    obsidian: u32,
+    geode: u32,
+    ore_robot: u32,
+    clay_robot: u32,
+    obsidian_robot: u32,
+    geode_robot: u32,
+}
+impl Run {
+    fn new() -> Run {
+        Run {
+            time: 0,
+            ore: 0,
+            clay: 0,
+            obsidian: 0,
+            geode: 0,
+            ore_robot: 1,
+            clay_robot: 0,
+            obsidian_robot: 0,
+            geode_robot: 0,
+        }
+    }
+    // Pushes the runs of the next minute to `next`
+    fn next(&self, blueprint: &Blueprint, next: &mut Vec<Run>) {
+        let start = next.len();
+        //// Possible creation
+        // None
+        next.push(self.clone());
+        // Robots beyond the most of a resource any robot costs are useless:
+        // a single robot can be made each minute
+        let max_ore = blueprint
+            .ore_robot
+            .max(blueprint.clay_robot)
+            .max(blueprint.obsidian_robot.0)
+            .max(blueprint.geode_robot.0);
+        // Create ore robot
+        if self.ore >= blueprint.ore_robot && self.ore_robot < max_ore {
+            let mut run = self.clone();
+            run.ore -= blueprint.ore_robot;
+            run.ore_robot += 1;
+            next.push(run);
+        }
+        // Create clay robot
+        if self.ore >= blueprint.clay_robot && self.clay_robot < blueprint.obsidian_robot.1 {
+            let mut run = self.clone();
+            run.ore -= blueprint.clay_robot;
+            run.clay_robot += 1;
+            next.push(run);
+        }
+        // Create obsidiant robot
+        if self.ore >= blueprint.obsidian_robot.0
+            && self.clay >= blueprint.obsidian_robot.1
+            && self.obsidian_robot < blueprint.geode_robot.1
+        {
+            let mut run = self.clone();
+            run.ore -= blueprint.obsidian_robot.0;
+            run.clay -= blueprint.obsidian_robot.1;
+            run.obsidian_robot += 1;
+            next.push(run);
+        }
+        // Create geode robot
+        if self.can_create_geode_robot(blueprint) {
+            let mut run = self.clone();
+            run.ore -= blueprint.geode_robot.0;
+            run.obsidian -= blueprint.geode_robot.1;
+            run.geode_robot += 1;
+            next.push(run);
+        }
+        // advance time and produce ore based on start of this time configuration, so self.
+        next[start..].iter_mut().for_each(|run| {
+            run.time += 1;
+            run.ore += self.ore_robot;
+            run.clay += self.clay_robot;
+            run.obsidian += self.obsidian_robot;
+            run.geode += self.geode_robot;
+        });
+    }
+    fn can_create_geode_robot(&self, blueprint: &Blueprint) -> bool {
+        self.ore >= blueprint.geode_robot.0 && self.obsidian >= blueprint.geode_robot.1
+    }
+    // Return a maximum potential score for this score.
+    // Guaranteeing it cannot be exceeded
+    fn potential_max_score(&self, blueprint: &Blueprint, end_time: &u32) -> u32 {
+        if self.can_create_geode_robot(blueprint) {
+            self.geode
+                + (0..(end_time - self.time))
+                    .map(|i| self.geode_robot + i)
+                    .sum::<u32>()
+        } else {
+            self.geode
+                + self.geode_robot
+                + (0..(end_time - 1 - self.time))
+                    .map(|i| self.geode_robot + i)
+                    .sum::<u32>()
+        }
+    }
+}
+impl Record for Run {
+    fn record(&self, numbers: &mut Vec<u64>) {
+        let resources = (self.ore, self.clay, self.obsidian);
+        let robots = (self.ore_robot, self.clay_robot, self.obsidian_robot);
+        (self.time, self.geode, self.geode_robot).record(numbers);
+        (resources, robots).record(numbers);
+    }
+    fn restore(numbers: &mut impl Iterator<Item = u64>) -> Option<Run> {
+        let (time, geode, geode_robot) = Record::restore(numbers)?;
+        let ((ore, clay, obsidian), (ore_robot, clay_robot, obsidian_robot)) =
+            Record::restore(numbers)?;
+        Some(Run {
+            time,
+            ore,
+            clay,
+            obsidian,
+            geode,
+            ore_robot,
+            clay_robot,
+            obsidian_robot,
+            geode_robot,
+        })
+    }
+}
+
+// Geodes opened by the end time, for a blueprint
+struct Geodes<'a> {
+    blueprint: &'a Blueprint,
+    end_time: u32,
+}
+
+impl Maximize for Geodes<'_> {
+    type State = Run;
+    type Score = u32;
+    type Key = ();
+    type Mark = ();
+
+    fn successors(&self, run: &Run, next: &mut Vec<Run>) {
+        if run.time < self.end_time {
+            run.next(self.blueprint, next);
+        }
+    }
+    // Doing nothing until the end
+    fn score(&self, run: &Run) -> u32 {
+        run.geode + (self.end_time - run.time) * run.geode_robot
+    }
+    fn upper_bound(&self, run: &Run) -> u32 {
+        if run.time == self.end_time {
+            run.geode
+        } else {
+            run.potential_max_score(self.blueprint, &self.end_time)
+        }
+    }
+}
+
+/// Most geodes a blueprint opens by the end time.
+pub fn best_geodes(blueprint: &Blueprint, end_time: u32) -> u32 {
+    let geodes = Geodes {
+        blueprint,
+        end_time,
+    };
+    let (best, stats) = search::branch_and_bound(&geodes, Run::new());
+    let best = best.map_or(0, |(score, _)| score);
+    info!(
+        "Blueprint {}: {} in {} iterations",
+        blueprint.id, best, stats.iterations
+    );
+    if let Some(abort) = stats.aborted {
+        info!("Stopped by {}, best so far", abort);
+    }
+    best
+}
+
+pub fn parse(content: &str) -> Result<Vec<Blueprint>, ParseError> {
+    let blueprints = lines(content)
+        .map(Blueprint::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    info!("N blueprints: {}", blueprints.len());
+    Ok(blueprints)
+}
+
+common::params! {
+    pub struct Params {
+        /// Minutes to open geodes in part 1
+        pub minutes1: u32 = 24,
+        /// Minutes to open geodes in part 2
+        pub minutes2: u32 = 32,
+        /// Blueprints left uneaten by the elephants, used by part 2 ( all of a shorter input )
+        pub blueprints2: usize = 3,
+    }
+}
+
+pub fn part1(blueprints: &[Blueprint], params: &Params) -> u32 {
+    let mut quality_total = 0;
+    for blueprint in blueprints.iter() {
+        let geodes = best_geodes(blueprint, params.minutes1);
+        quality_total += blueprint.id * geodes;
+        explain!(
+            "Blueprint {}: {} geodes, quality {} * {} = {}, total {}",
+            blueprint.id,
+            geodes,
+            blueprint.id,
+            geodes,
+            blueprint.id * geodes,
+            quality_total
+        );
+    }
+    quality_total
+}
+
+pub fn part2(blueprints: &[Blueprint], params: &Params) -> u32 {
+    let mut part_2_score = 1;
+    for blueprint in blueprints.iter().take(params.blueprints2) {
+        let geodes = best_geodes(blueprint, params.minutes2);
+        part_2_score *= geodes;
+        explain!(
+            "Blueprint {}: {} geodes, product {}",
+            blueprint.id,
+            geodes,
+            part_2_score
+        );
+    }
+    part_2_score
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<Blueprint>;
+    type Params = Params;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(content: &str) -> Result<Vec<Blueprint>, ParseError> {
+        parse(content)
+    }
+
+    fn check(_: &Vec<Blueprint>, params: &Params) -> Result<(), String> {
+        if params.minutes1 == 0 || params.minutes2 == 0 {
+            return Err(String::from("minutes must be at least 1"));
+        }
+        if params.blueprints2 == 0 {
+            return Err(String::from("blueprints2 must be at least 1"));
+        }
+        Ok(())
+    }
+
+    fn part1(input: &Vec<Blueprint>, params: &Params) -> u32 {
+        part1(input, params)
+    }
+
+    fn part2(input: &Vec<Blueprint>, params: &Params) -> u32 {
+        part2(input, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap(), &Params::default()), 33);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap(), &Params::default()), 56 * 62);
+    }
+
+    #[test]
+    fn check_params() {
+        use common::Solver;
+        let blueprints = parse(EXAMPLE).unwrap();
+        let check = |params| Solution::check(&blueprints, &params);
+        // The example has only 2 blueprints, part 2 takes them both
+        let params = Params::default();
+        assert!(check(params.clone()).is_ok());
+        let part1 = common::solve::<Solution>(EXAMPLE, &common::Settings::default(), 1);
+        assert_eq!(part1.unwrap(), "33");
+        assert!(check(Params {
+            minutes1: 0,
+            ..params.clone()
+        })
+        .is_err());
+        assert!(check(Params {
+            blueprints2: 0,
+            ..params
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use common::{explain, lines, ParseError};
+use log::{debug, info, trace};
+
+fn grove_coordinates(numbers: &[i64], decryption_key: i64, n_loop: u32) -> i64 {
+    // Same value can be present multiple time, so keep line index to desambiguate
+    let mut numbers = numbers
+        .iter()
+        .enumerate()
+        .map(|(idx, v)| (idx, v * decryption_key))
+        .collect::<Vec<_>>();
+    let size = numbers.len() as i64;
+    info!("Numbers size: {}", size);
+    trace!("Start: {:?}", numbers);
+    let decryption_order = numbers.clone();
+    for n in 0..n_loop {
+        for &value in decryption_order.iter() {
+            trace!("{:?}", numbers);
+            trace!("Value: {}", value.1);
+            let index = numbers
+                .iter()
+                .enumerate()
+                .find(|(_, &v)| v == value)
+                .unwrap()
+                .0;
+            trace!("Index: {}", index);
+            numbers.remove(index);
+
+            let new_index = (index as i64 + value.1).rem_euclid(size - 1);
+            numbers.insert(new_index as usize, value);
+            trace!("New index: {}", new_index);
+            // Every move of the first round, the next ones only summarized
+            if n == 0 {
+                explain!("{} moves from index {} to {}", value.1, index, new_index);
+            }
+        }
+        debug!("End loop {} {:?}", n, numbers);
+        let shown = numbers.iter().take(7).map(|v| v.1.to_string());
+        explain!(
+            "After round {}: {}{}",
+            n + 1,
+            shown.collect::<Vec<_>>().join(", "),
+            if numbers.len() > 7 { ", ..." } else { "" }
+        );
+    }
+    let index_0 = numbers
+        .iter()
+        .enumerate()
+        .find(|(_, &v)| v.1 == 0)
+        .unwrap()
+        .0;
+    let v1000 = numbers[(index_0 + 1000) % size as usize].1;
+    let v2000 = numbers[(index_0 + 2000) % size as usize].1;
+    let v3000 = numbers[(index_0 + 3000) % size as usize].1;
+    info!(
+        "id 0: {} => {} + {} + {} = {}",
+        index_0,
+        v1000,
+        v2000,
+        v3000,
+        v1000 + v2000 + v3000
+    );
+    explain!(
+        "0 at index {}, 1000th, 2000th and 3000th after: {} + {} + {} = {}",
+        index_0,
+        v1000,
+        v2000,
+        v3000,
+        v1000 + v2000 + v3000
+    );
+    v1000 + v2000 + v3000
+}
+
+pub fn parse(content: &str) -> Result<Vec<i64>, ParseError> {
+    let numbers = lines(content)
+        .map(|l| l.parse::<i64>("a number"))
+        .collect::<Result<Vec<_>, _>>()?;
+    // The mixing needs a single 0
+    if numbers.iter().filter(|&&v| v == 0).count() != 1 {
+        let n_line = lines(content).count();
+        return Err(ParseError::new(
+            n_line + 1,
+            1,
+            "a file with exactly one 0",
+            "",
+        ));
+    }
+    Ok(numbers)
+}
+
+common::params! {
+    pub struct Params {
+        /// Decryption key multiplying the numbers in part 2
+        pub key: i64 = 811_589_153,
+        /// Mixing rounds of part 2
+        pub rounds: u32 = 10,
+    }
+}
+
+pub fn part1(numbers: &[i64], _params: &Params) -> i64 {
+    grove_coordinates(numbers, 1, 1)
+}
+
+pub fn part2(numbers: &[i64], params: &Params) -> i64 {
+    grove_coordinates(numbers, params.key, params.rounds)
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<i64>;
+    type Params = Params;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(content: &str) -> Result<Vec<i64>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Vec<i64>, params: &Params) -> i64 {
+        part1(input, params)
+    }
+
+    fn part2(input: &Vec<i64>, params: &Params) -> i64 {
+        part2(input, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE_A: &str = include_str!("../exampleA.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap(), &Params::default()), 3);
+    }
+
+    #[test]
+    fn example_a_part1() {
+        assert_eq!(part1(&parse(EXAMPLE_A).unwrap(), &Params::default()), 7);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            part2(&parse(EXAMPLE).unwrap(), &Params::default()),
+            1623178306
+        );
+    }
+
+    #[test]
+    fn example_a_part2() {
+        assert_eq!(
+            part2(&parse(EXAMPLE_A).unwrap(), &Params::default()),
+            6492713224
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+
+use common::{checked, explain, lines, ParseError, Token};
+use log::trace;
+
+#[derive(Clone, Debug)]
+pub enum Op {
+    Value(u64),
+    Add(String, String),
+    Sub(String, String),
+    Mul(String, String),
+    Div(String, String),
+    Humn,
+}
+impl Op {
+    fn parse(job: Token) -> Result<Op, ParseError> {
+        let content = job.words();
+        match content.len() {
+            3 => {
+                let (m0, m1) = (content[0].text.to_string(), content[2].text.to_string());
+                match content[1].text {
+                    "+" => Ok(Op::Add(m0, m1)),
+                    "-" => Ok(Op::Sub(m0, m1)),
+                    "*" => Ok(Op::Mul(m0, m1)),
+                    "/" => Ok(Op::Div(m0, m1)),
+                    _ => Err(content[1].error("`+`, `-`, `*` or `/`")),
+                }
+            }
+            1 => Ok(Op::Value(content[0].parse::<u64>("a number")?)),
+            _ => Err(job.error("a number or an operation like `aaaa + bbbb`")),
+        }
+    }
+    // Operands and operator, None for a value
+    fn operation(&self) -> Option<(&str, &str, &str)> {
+        match self {
+            Op::Add(m0, m1) => Some((m0, "+", m1)),
+            Op::Sub(m0, m1) => Some((m0, "-", m1)),
+            Op::Mul(m0, m1) => Some((m0, "*", m1)),
+            Op::Div(m0, m1) => Some((m0, "/", m1)),
+            Op::Value(_) | Op::Humn => None,
+        }
+    }
+}
+#[derive(Clone, Debug)]
+pub struct World {
+    pub monkeys: HashMap<String, Op>,
+    pub part_2_root: (String, String),
+}
+impl World {
+    pub fn new(content: &str) -> Result<World, ParseError> {
+        let mut monkeys = HashMap::new();
+        let mut operands = vec![];
+        for line in lines(content) {
+            let split = line.split_n(":", 2, "`name: job`")?;
+            let op = Op::parse(split[1])?;
+            if let Op::Add(..) | Op::Sub(..) | Op::Mul(..) | Op::Div(..) = op {
+                let words = split[1].words();
+                operands.extend([words[0], words[2]]);
+            }
+            monkeys.insert(split[0].text.to_string(), op);
+        }
+        if let Some(unknown) = operands.iter().find(|m| !monkeys.contains_key(m.text)) {
+            return Err(unknown.error("the name of a monkey"));
+        }
+        let n_line = lines(content).count();
+        for name in ["root", "humn"] {
+            if !monkeys.contains_key(name) {
+                let expected = format!("a `{}` monkey", name);
+                return Err(ParseError::new(n_line + 1, 1, &expected, ""));
+            }
+        }
+        if let Some(Op::Value(_)) = monkeys.get("root") {
+            return Err(ParseError::new(
+                n_line + 1,
+                1,
+                "an operation for `root`",
+                "",
+            ));
+        }
+        Ok(World {
+            monkeys,
+            part_2_root: (String::from(""), String::from("")),
+        })
+    }
+    // For part 2, root compares its two monkeys and humn is the unknown value
+    pub fn with_humn(&self) -> World {
+        let mut world = self.clone();
+        world.part_2_root = match world.monkeys.remove("root").unwrap() {
+            Op::Add(m0, m1) | Op::Sub(m0, m1) | Op::Mul(m0, m1) | Op::Div(m0, m1) => (m0, m1),
+            op => panic!("Unexpected root operation {:?}", op),
+        };
+        world.monkeys.insert(String::from("humn"), Op::Humn);
+        world
+    }
+    pub fn get_value(&self, name: &str) -> Option<u64> {
+        let context = || format!("monkey {}", name);
+        match self.monkeys[name].clone() {
+            Op::Value(v) => Some(v),
+            Op::Humn => {
+                trace!("get Humn");
+                None
+            }
+            Op::Add(m0, m1) => {
+                let v0 = self.get_value(&m0);
+                let v1 = self.get_value(&m1);
+                match (v0, v1) {
+                    (Some(v0), Some(v1)) => Some(checked::add(v0, v1, context)),
+                    _ => None,
+                }
+            }
+            Op::Sub(m0, m1) => {
+                let v0 = self.get_value(&m0);
+                let v1 = self.get_value(&m1);
+                match (v0, v1) {
+                    (Some(v0), Some(v1)) => Some(checked::sub(v0, v1, context)),
+                    _ => None,
+                }
+            }
+            Op::Mul(m0, m1) => {
+                let v0 = self.get_value(&m0);
+                let v1 = self.get_value(&m1);
+                match (v0, v1) {
+                    (Some(v0), Some(v1)) => Some(checked::mul(v0, v1, context)),
+                    _ => None,
+                }
+            }
+            Op::Div(m0, m1) => {
+                let v0 = self.get_value(&m0);
+                let v1 = self.get_value(&m1);
+                match (v0, v1) {
+                    (Some(v0), Some(v1)) => Some(checked::div(v0, v1, context)),
+                    _ => None,
+                }
+            }
+        }
+    }
+    pub fn solve_humn(&mut self) -> u64 {
+        let mut value = self.get_value(&self.part_2_root.1).unwrap();
+        let mut current = self.part_2_root.0.clone();
+        explain!("root: {} = {} = {}", current, self.part_2_root.1, value);
+        loop {
+            let op = self.monkeys[&current].clone();
+            trace!("Current: {}  -> {:?}", current, op);
+            let name = current.clone();
+            let target = value;
+            let context = || format!("monkey {}, solving for humn", name);
+            match op {
+                Op::Humn => {
+                    return value;
+                }
+                Op::Value(_) => {
+                    panic!()
+                }
+                Op::Add(m0, m1) => {
+                    let v0 = self.get_value(&m0);
+                    let v1 = self.get_value(&m1);
+                    assert!(v0.is_some() || v1.is_some());
+                    if let Some(v0) = v0 {
+                        current = m1;
+                        value = checked::sub(value, v0, context);
+                    } else {
+                        current = m0;
+                        value = checked::sub(value, v1.unwrap(), context);
+                    }
+                }
+                Op::Sub(m0, m1) => {
+                    let v0 = self.get_value(&m0);
+                    let v1 = self.get_value(&m1);
+                    assert!(v0.is_some() || v1.is_some());
+                    if let Some(v0) = v0 {
+                        current = m1;
+                        value = checked::sub(v0, value, context);
+                    } else {
+                        current = m0;
+                        value = checked::add(value, v1.unwrap(), context);
+                    }
+                }
+                Op::Mul(m0, m1) => {
+                    let v0 = self.get_value(&m0);
+                    let v1 = self.get_value(&m1);
+                    assert!(v0.is_some() || v1.is_some());
+                    if let Some(v0) = v0 {
+                        current = m1;
+                        value = checked::div(value, v0, context);
+                    } else {
+                        current = m0;
+                        value = checked::div(value, v1.unwrap(), context);
+                    }
+                }
+                Op::Div(m0, m1) => {
+                    let v0 = self.get_value(&m0);
+                    let v1 = self.get_value(&m1);
+                    assert!(v0.is_some() || v1.is_some());
+                    if let Some(v0) = v0 {
+                        current = m1;
+                        value = checked::div(v0, value, context);
+                    } else {
+                        current = m0;
+                        value = checked::mul(value, v1.unwrap(), context);
+                    }
+                }
+            }
+            if explain::enabled() {
+                // The known operand by its value
+                let (m0, symbol, m1) = self.monkeys[&name].operation().unwrap();
+                let side = |m: &str| match self.get_value(m) {
+                    Some(v) => v.to_string(),
+                    None => m.to_string(),
+                };
+                explain!(
+                    "{}: {} {} {} = {}, so {} = {}",
+                    name,
+                    side(m0),
+                    symbol,
+                    side(m1),
+                    target,
+                    current,
+                    value
+                );
+            }
+        }
+    }
+}
+pub fn parse(content: &str) -> Result<World, ParseError> {
+    World::new(content)
+}
+
+pub fn part1(world: &World) -> u64 {
+    let root = world.get_value("root").unwrap();
+    if let Some((m0, symbol, m1)) = world.monkeys["root"].operation() {
+        explain!(
+            "root: {} {} {} = {} {} {} = {}",
+            m0,
+            symbol,
+            m1,
+            world.get_value(m0).unwrap(),
+            symbol,
+            world.get_value(m1).unwrap(),
+            root
+        );
+    }
+    root
+}
+
+pub fn part2(world: &World) -> u64 {
+    world.with_humn().solve_humn()
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = World;
+    type Params = ();
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(content: &str) -> Result<World, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &World, _: &()) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &World, _: &()) -> u64 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 152);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 301);
+    }
+
+    #[test]
+    fn checked_overflow() {
+        let world = parse("root: aaaa - humn\naaaa: 3\nhumn: 5\n").unwrap();
+        checked::set(true);
+        let overflow = checked::catch(|| part1(&world)).unwrap_err();
+        checked::set(false);
+        assert_eq!(overflow.to_string(), "`3 - 5` out of u64, in monkey root");
+    }
+}
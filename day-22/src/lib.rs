@@ -0,0 +1,619 @@
+use std::{collections::HashSet, hash::Hash};
+
+use common::{explain, sections, Image, ParseError, Token};
+use grid::Grid;
+use log::{debug, info, trace};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Floor,
+}
+// 3D vector of the folded cube
+type Vector = [i32; 3];
+
+fn dot(a: Vector, b: Vector) -> i32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+fn neg(a: Vector) -> Vector {
+    [-a[0], -a[1], -a[2]]
+}
+
+// A face of the folded cube: its place in the net, by ( column, row ) of
+// faces, and the directions of its x and y axes and of its outside in 3D
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+struct Face {
+    pos: (i32, i32),
+    right: Vector,
+    down: Vector,
+    normal: Vector,
+}
+impl Face {
+    // The face over the edge in a direction: folded down behind the edge,
+    // its outside faces that direction
+    fn neighbour(&self, dir: Dir) -> Face {
+        let (x, y) = self.pos;
+        let Face {
+            right,
+            down,
+            normal,
+            ..
+        } = *self;
+        match dir {
+            Dir::East => Face {
+                pos: (x + 1, y),
+                right: neg(normal),
+                down,
+                normal: right,
+            },
+            Dir::West => Face {
+                pos: (x - 1, y),
+                right: normal,
+                down,
+                normal: neg(right),
+            },
+            Dir::South => Face {
+                pos: (x, y + 1),
+                right,
+                down: neg(normal),
+                normal: down,
+            },
+            Dir::North => Face {
+                pos: (x, y - 1),
+                right,
+                down: normal,
+                normal: neg(down),
+            },
+        }
+    }
+    fn direction(&self, dir: Dir) -> Vector {
+        match dir {
+            Dir::East => self.right,
+            Dir::South => self.down,
+            Dir::West => neg(self.right),
+            Dir::North => neg(self.down),
+        }
+    }
+}
+
+// The faces of the cube folded from the map and their width, None if the
+// map is not the net of a cube
+fn fold(blocks: &Grid<Tile>) -> Option<(i32, Vec<Face>)> {
+    let tiles = blocks.iter().filter(|(_, &t)| t != Tile::Empty).count();
+    let size = (1..).find(|s| 6 * s * s >= tiles).unwrap();
+    if 6 * size * size != tiles {
+        return None;
+    }
+    let size = size as i32;
+    let places = blocks
+        .iter()
+        .filter(|(_, &t)| t != Tile::Empty)
+        .map(|((x, y), _)| (x / size, y / size))
+        .collect::<HashSet<_>>();
+    // Six full faces, folded from the first one
+    if places.len() != 6 {
+        return None;
+    }
+    let first = *places.iter().min_by_key(|&&(x, y)| (y, x)).unwrap();
+    let mut faces = vec![Face {
+        pos: first,
+        right: [1, 0, 0],
+        down: [0, 1, 0],
+        normal: [0, 0, -1],
+    }];
+    let mut i = 0;
+    while i < faces.len() {
+        for dir in [Dir::East, Dir::South, Dir::West, Dir::North] {
+            let next = faces[i].neighbour(dir);
+            if places.contains(&next.pos) && faces.iter().all(|f| f.pos != next.pos) {
+                faces.push(next);
+            }
+        }
+        i += 1;
+    }
+    let normals = faces.iter().map(|f| f.normal).collect::<HashSet<_>>();
+    (normals.len() == 6).then_some((size, faces))
+}
+
+#[derive(Clone, Debug)]
+pub struct World {
+    pub blocks: Grid<Tile>,
+    pub cube: bool,
+    /// Width of the cube faces, 0 if the map does not fold into a cube
+    pub cube_size: i32,
+    faces: Vec<Face>,
+    pub seen: HashSet<(String, Dir)>,
+}
+impl World {
+    pub fn new(blocks: Grid<Tile>, cube: bool) -> World {
+        let (cube_size, faces) = fold(&blocks).unwrap_or((0, vec![]));
+        World {
+            blocks,
+            cube,
+            cube_size,
+            faces,
+            seen: HashSet::new(),
+        }
+    }
+    /// Whether the map folds into the cube of part 2
+    pub fn is_cube(&self) -> bool {
+        !self.faces.is_empty()
+    }
+    /// Error of part 2 on a map that does not fold into a cube, located on
+    /// the line after the map.
+    pub fn check_cube(&self) -> Result<(), ParseError> {
+        if self.is_cube() {
+            return Ok(());
+        }
+        let expected = "a map of the 6 square faces of a cube net, for part 2";
+        Err(ParseError::new(self.blocks.height() + 1, 1, expected, ""))
+    }
+    fn face(&self, x: i32, y: i32) -> &Face {
+        let pos = (x / self.cube_size, y / self.cube_size);
+        self.faces.iter().find(|f| f.pos == pos).unwrap()
+    }
+    /// Face of the cube at a position, by the letter of its place in the net
+    /// read row by row, and the position within the face.
+    pub fn get_face_pos(&self, x: i32, y: i32) -> (String, i32, i32) {
+        let size = self.cube_size;
+        let face = self.face(x, y).pos;
+        let mut places = self.faces.iter().map(|f| f.pos).collect::<Vec<_>>();
+        places.sort_by_key(|&(x, y)| (y, x));
+        let i = places.iter().position(|&p| p == face).unwrap();
+        let name = (b'A' + i as u8) as char;
+        (name.to_string(), x % size, y % size)
+    }
+    pub fn wrap(&self, x: i32, y: i32, dir: Dir) -> (i32, i32, Dir) {
+        let (mut new_x, mut new_y, mut new_dir) = (x, y, dir);
+        if self.cube {
+            // Cell centers are at odd coordinates, the cube spans -size..size
+            let size = self.cube_size;
+            let from = self.face(x, y);
+            let ahead = from.direction(dir);
+            let to = self.faces.iter().find(|f| f.normal == ahead).unwrap();
+            let (fx, fy) = (x % size, y % size);
+            // Center of the cell over the edge: from the cell left, one step
+            // down the side of the cube
+            let center: Vector = std::array::from_fn(|i| {
+                size * from.normal[i]
+                    + (2 * fx + 1 - size) * from.right[i]
+                    + (2 * fy + 1 - size) * from.down[i]
+                    - from.normal[i]
+                    + ahead[i]
+            });
+            let (tx, ty) = (
+                (dot(center, to.right) + size - 1) / 2,
+                (dot(center, to.down) + size - 1) / 2,
+            );
+            new_x = to.pos.0 * size + tx;
+            new_y = to.pos.1 * size + ty;
+            new_dir = [Dir::East, Dir::South, Dir::West, Dir::North]
+                .into_iter()
+                .find(|&d| to.direction(d) == neg(from.normal))
+                .unwrap();
+        } else {
+            if dir == Dir::East || dir == Dir::West {
+                trace!("Wrap from {} {} {:?} ", x, y, dir);
+                new_x = self.get_first_x(y, dir);
+                trace!("   -> {} {} ", new_x, new_y);
+            } else {
+                trace!("Wrap from {} {} {:?} ", x, y, dir);
+                new_y = self.get_first_y(x, dir);
+                trace!("   -> {} {} ", new_x, new_y);
+            }
+        }
+        (new_x, new_y, new_dir)
+    }
+    pub fn get_first_x(&self, y: i32, dir: Dir) -> i32 {
+        let row = self.blocks.row(y as usize);
+        let x = match dir {
+            Dir::East => row.iter().position(|&t| t != Tile::Empty),
+            Dir::West => row.iter().rposition(|&t| t != Tile::Empty),
+            _ => panic!(),
+        };
+        x.unwrap() as i32
+    }
+    pub fn get_first_y(&self, x: i32, dir: Dir) -> i32 {
+        let not_empty = |&y: &i32| self.blocks[(x, y)] != Tile::Empty;
+        let height = self.blocks.height() as i32;
+        let y = match dir {
+            Dir::South => (0..height).find(not_empty),
+            Dir::North => (0..height).rev().find(not_empty),
+            _ => panic!(),
+        };
+        y.unwrap()
+    }
+    pub fn forward(&mut self, x: i32, y: i32, dir: Dir) -> (i32, i32, Dir) {
+        let (mut new_x, mut new_y) = match dir {
+            Dir::East => (x + 1, y),
+            Dir::West => (x - 1, y),
+            Dir::South => (x, y + 1),
+            Dir::North => (x, y - 1),
+        };
+        let mut new_dir = dir;
+        let mut tile = self
+            .blocks
+            .get((new_x, new_y))
+            .copied()
+            .unwrap_or(Tile::Empty);
+        if tile == Tile::Empty {
+            (new_x, new_y, new_dir) = self.wrap(x, y, dir);
+            if self.cube {
+                let face_pos = self.get_face_pos(x, y);
+                let face = face_pos.0.clone();
+                if !self.seen.contains(&(face, dir)) {
+                    self.seen.insert((face_pos.0.clone(), dir));
+                    debug!(
+                        "WRAP {},{} {:?} {:?} -> {},{} {:?} {:?} ",
+                        x,
+                        y,
+                        face_pos,
+                        dir,
+                        new_x,
+                        new_y,
+                        self.get_face_pos(new_x, new_y),
+                        new_dir,
+                    );
+                }
+            }
+            tile = self.blocks[(new_x, new_y)];
+        }
+        match tile {
+            Tile::Floor => (new_x, new_y, new_dir),
+            Tile::Wall => (x, y, dir),
+            Tile::Empty => panic!(
+                "From {} {} {:?} -> {} ,{} with {:?}",
+                x, y, dir, new_x, new_y, tile
+            ),
+        }
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Dir {
+    West,
+    East,
+    South,
+    North,
+}
+impl Dir {
+    pub fn apply(&self, action: Action) -> Dir {
+        match action {
+            Action::Forward(_) => *self,
+            Action::CW => match self {
+                Dir::East => Dir::South,
+                Dir::South => Dir::West,
+                Dir::West => Dir::North,
+                Dir::North => Dir::East,
+            },
+            Action::CCW => match self {
+                Dir::East => Dir::North,
+                Dir::North => Dir::West,
+                Dir::West => Dir::South,
+                Dir::South => Dir::East,
+            },
+        }
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Action {
+    Forward(u32),
+    CW,
+    CCW,
+}
+impl Action {
+    pub fn parse(line: Token) -> Result<Vec<Action>, ParseError> {
+        let mut actions = vec![];
+        for part in line.split_inclusive(|c: char| !c.is_ascii_digit()) {
+            let (num, turn) = match part.text.char_indices().last() {
+                Some((i, c)) if !c.is_ascii_digit() => part.split_at(i),
+                _ => part.split_at(part.text.len()),
+            };
+            if !num.is_empty() {
+                let count = num.parse::<u32>("a number of steps")?;
+                actions.push(Action::Forward(count));
+            }
+            match turn.text {
+                "" => (),
+                "R" => actions.push(Action::CW),
+                "L" => actions.push(Action::CCW),
+                _ => return Err(turn.error("a number of steps, `R` or `L`")),
+            }
+        }
+        Ok(actions)
+    }
+}
+#[derive(Clone, Debug)]
+pub struct Player {
+    pub y: i32,
+    pub x: i32,
+    pub facing: Dir,
+    pub actions: Vec<Action>,
+    /// Positions and facings walked through, from the start
+    pub trail: Vec<(i32, i32, Dir)>,
+}
+impl Player {
+    pub fn new(actions: &[Action], world: &World) -> Player {
+        let x = world
+            .blocks
+            .row(0)
+            .iter()
+            .position(|&t| t == Tile::Floor)
+            .unwrap();
+        Player {
+            y: 0,
+            x: x as i32,
+            facing: Dir::East,
+            actions: actions.to_vec(),
+            trail: vec![(x as i32, 0, Dir::East)],
+        }
+    }
+    pub fn advance(&mut self, world: &mut World) -> bool {
+        if self.actions.is_empty() {
+            return false;
+        }
+        let action = self.actions.remove(0);
+        if let Action::Forward(x) = action {
+            for _ in 0..x {
+                (self.x, self.y, self.facing) = world.forward(self.x, self.y, self.facing);
+                self.trail.push((self.x, self.y, self.facing));
+            }
+        } else {
+            self.facing = self.facing.apply(action);
+            self.trail.push((self.x, self.y, self.facing));
+        }
+        true
+    }
+    pub fn password(&self) -> i32 {
+        1000 * (self.y + 1)
+            + 4 * (self.x + 1)
+            + match self.facing {
+                Dir::East => 0,
+                Dir::South => 1,
+                Dir::West => 2,
+                Dir::North => 3,
+            }
+    }
+    fn actions_to_string(&self) -> String {
+        let mut s = String::from("");
+        for action in self.actions.iter() {
+            let ss = match action {
+                Action::Forward(f) => f.to_string(),
+                Action::CW => String::from("R"),
+                Action::CCW => String::from("L"),
+            };
+            s += &ss;
+        }
+        s
+    }
+}
+fn password(input: &(World, Vec<Action>), cube: bool) -> i32 {
+    let player = walk(input, cube);
+    explain!(
+        "Row {}, column {}, facing {:?}: 1000 * {} + 4 * {} + {} = {}",
+        player.y + 1,
+        player.x + 1,
+        player.facing,
+        player.y + 1,
+        player.x + 1,
+        player.password() % 4,
+        player.password()
+    );
+    player.password()
+}
+
+/// The player at the end of the path, on the flat map or on the cube.
+pub fn walk((world, actions): &(World, Vec<Action>), cube: bool) -> Player {
+    let mut world = world.clone();
+    world.cube = cube;
+    assert!(!cube || world.is_cube(), "cube net checked by check_part");
+    info!("World Cube:{} {}", world.cube, world.cube_size);
+    let mut player = Player::new(actions, &world);
+    trace!("{}", player.actions_to_string());
+    debug!("Start -> {:?}", player);
+    loop {
+        let (action, start) = (player.actions.first().copied(), player.trail.len() - 1);
+        if !player.advance(&mut world) {
+            break;
+        } else {
+            trace!("  {:?}", player);
+        }
+        if let Some(Action::Forward(count)) = action {
+            let (x, y, facing) = player.trail[start];
+            let moved = player.trail[start..]
+                .windows(2)
+                .filter(|w| (w[0].0, w[0].1) != (w[1].0, w[1].1))
+                .count();
+            explain!(
+                "Forward {} facing {:?} from ({}, {}): at ({}, {}) facing {:?}{}",
+                count,
+                facing,
+                x,
+                y,
+                player.x,
+                player.y,
+                player.facing,
+                if moved < count as usize {
+                    format!(", stopped by a wall after {}", moved)
+                } else {
+                    String::new()
+                }
+            );
+        }
+    }
+    info!("End -> {} {} {:?}", player.x, player.y, player.facing);
+    player
+}
+
+/// The map with the path walked, its color showing the facing: red to the
+/// east, green to the south, blue to the west and yellow to the north.
+pub fn image(input: &(World, Vec<Action>), cube: bool) -> Image {
+    let mut colors = input.0.blocks.map(|tile| match tile {
+        Tile::Empty => [0, 0, 0],
+        Tile::Floor => [255, 255, 255],
+        Tile::Wall => [128, 128, 128],
+    });
+    for &(x, y, facing) in walk(input, cube).trail.iter() {
+        colors[(x, y)] = match facing {
+            Dir::East => [220, 40, 40],
+            Dir::South => [40, 180, 40],
+            Dir::West => [40, 80, 220],
+            Dir::North => [230, 200, 30],
+        };
+    }
+    colors.pixmap(|&color| color)
+}
+
+pub fn parse(content: &str) -> Result<(World, Vec<Action>), ParseError> {
+    // The map, then the path after an empty line
+    let sections = sections(content);
+    let (map, path) = match &sections[..] {
+        [map, path] => (map, path),
+        [map] => {
+            let n_line = map.last().unwrap().line;
+            return Err(ParseError::new(
+                n_line + 2,
+                1,
+                "a path after an empty line",
+                "",
+            ));
+        }
+        [] => return Err(ParseError::new(1, 1, "a map", "")),
+        [_, _, extra, ..] => return Err(extra[0].error("the end of the input")),
+    };
+    // The map keeps the spaces at the start of its lines
+    let map_start = map[0].text.as_ptr() as usize - content.as_ptr() as usize;
+    let map_end = path[0].text.as_ptr() as usize - content.as_ptr() as usize;
+    let blocks = Grid::parse_padded(
+        &content[map_start..map_end],
+        Tile::Empty,
+        "` `, `.` or `#`",
+        |c| match c {
+            ' ' => Some(Tile::Empty),
+            '.' => Some(Tile::Floor),
+            '#' => Some(Tile::Wall),
+            _ => None,
+        },
+    )?;
+    if let Some(line) = path.get(1) {
+        return Err(line.error("a single line path"));
+    }
+    let actions = Action::parse(path[0])?;
+    Ok((World::new(blocks, false), actions))
+}
+
+pub fn part1(input: &(World, Vec<Action>)) -> i32 {
+    password(input, false)
+}
+
+pub fn part2(input: &(World, Vec<Action>)) -> i32 {
+    password(input, true)
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = (World, Vec<Action>);
+    type Params = ();
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(content: &str) -> Result<(World, Vec<Action>), ParseError> {
+        parse(content)
+    }
+
+    fn check_part(input: &(World, Vec<Action>), _: &(), part: u8) -> Result<(), ParseError> {
+        match part {
+            2 => input.0.check_cube(),
+            _ => Ok(()),
+        }
+    }
+
+    fn part1(input: &(World, Vec<Action>), _: &()) -> i32 {
+        part1(input)
+    }
+
+    fn part2(input: &(World, Vec<Action>), _: &()) -> i32 {
+        part2(input)
+    }
+
+    fn images(input: &(World, Vec<Action>), _: &(), part: u8) -> Vec<(&'static str, Image)> {
+        vec![("path", image(input, part == 2))]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 6032);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 5031);
+    }
+
+    // A wall free cube, its net drawn with `#` for faces
+    fn empty_cube(net: &[&str], size: usize) -> World {
+        let mut content = String::new();
+        for row in net {
+            for _ in 0..size {
+                for c in row.chars() {
+                    let tile = if c == '#' { '.' } else { ' ' };
+                    content += &tile.to_string().repeat(size);
+                }
+                content.push('\n');
+            }
+        }
+        content += "\n1\n";
+        let (mut world, _) = parse(&content).unwrap();
+        world.cube = true;
+        world
+    }
+
+    #[test]
+    fn cube_loops() {
+        let nets: [&[&str]; 4] = [
+            &["  #", "###", "  ##"],
+            &[" ##", " #", "##", "#"],
+            &["###", "  ###"],
+            &[" #", "###", " #", " #"],
+        ];
+        for net in nets {
+            for size in [1, 2, 3, 4, 7] {
+                let mut world = empty_cube(net, size);
+                assert_eq!(world.cube_size, size as i32, "{:?}", net);
+                // Straight ahead around the cube, back to the start
+                let floor = world.blocks.iter().filter(|(_, &t)| t == Tile::Floor);
+                for (x, y) in floor.map(|(pos, _)| pos).collect::<Vec<_>>() {
+                    for dir in [Dir::East, Dir::South, Dir::West, Dir::North] {
+                        let mut state = (x, y, dir);
+                        for _ in 0..4 * size {
+                            state = world.forward(state.0, state.1, state.2);
+                        }
+                        assert_eq!(state, (x, y, dir), "{:?} of {}", net, size);
+                    }
+                }
+            }
+        }
+        let flat = parse("...\n...\n\n2R1\n").unwrap();
+        assert!(!flat.0.is_cube());
+        assert_eq!(flat.0.check_cube().unwrap_err().line, 3);
+        assert_eq!(part1(&flat), 2 * 1000 + 3 * 4 + 1);
+    }
+
+    #[test]
+    fn example_trail() {
+        let player = walk(&parse(EXAMPLE).unwrap(), true);
+        assert_eq!(player.trail.first(), Some(&(8, 0, Dir::East)));
+        assert_eq!(
+            player.trail.last(),
+            Some(&(player.x, player.y, player.facing))
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+use common::{explain, Image, ParseError, Simulation};
+use grid::{SparseGrid, NEIGHBOURS8};
+use log::{debug, info, trace};
+
+#[derive(Clone)]
+pub struct World {
+    pub elves: SparseGrid<()>,
+    #[allow(clippy::type_complexity)]
+    pub moves: Vec<(Vec<(i32, i32)>, i32, i32)>,
+    /// Rounds done
+    pub round: u32,
+}
+impl World {
+    pub fn new(content: &str) -> Result<World, ParseError> {
+        let moves = vec![
+            // Nothing
+            (NEIGHBOURS8.to_vec(), 0, 0),
+            // North
+            (vec![(-1, -1), (0, -1), (1, -1)], 0, -1),
+            // South
+            (vec![(-1, 1), (0, 1), (1, 1)], 0, 1),
+            // West
+            (vec![(-1, -1), (-1, 0), (-1, 1)], -1, 0),
+            // East
+            (vec![(1, -1), (1, 0), (1, 1)], 1, 0),
+        ];
+        let elves = SparseGrid::parse(content, "`#` or `.`", |c| match c {
+            '#' => Ok(Some(())),
+            '.' => Ok(None),
+            _ => Err(()),
+        })?;
+        Ok(World {
+            elves,
+            moves,
+            round: 0,
+        })
+    }
+    pub fn round(&mut self) -> bool {
+        // Plan moves
+        let mut planned_moves = HashMap::new();
+        let mut dests = HashMap::new();
+        for (elf_x, elf_y) in self.elves.positions() {
+            let mut found = false;
+            for (checks, dx, dy) in self.moves.iter() {
+                if checks
+                    .iter()
+                    .all(|(dx, dy)| !self.elves.contains((elf_x + dx, elf_y + dy)))
+                {
+                    let dest = (elf_x + dx, elf_y + dy);
+                    planned_moves.insert((elf_x, elf_y), dest);
+                    dests.entry(dest).and_modify(|v| *v += 1).or_insert(1);
+                    found = true;
+                    break;
+                }
+            }
+            if !found {
+                let dest = (elf_x, elf_y);
+                planned_moves.insert((elf_x, elf_y), dest);
+                dests.entry(dest).and_modify(|v| *v += 1).or_insert(1);
+            }
+        }
+        trace!("Planned: {:?}", planned_moves);
+        trace!("Dest: {:?}", dests);
+        // Execute moves
+        let mut n_moved = 0;
+        let mut new_elves = SparseGrid::new();
+        for (&from, &dest) in planned_moves.iter() {
+            if dests[&dest] == 1 {
+                new_elves.insert(dest, ());
+                if from != dest {
+                    n_moved += 1;
+                }
+            } else {
+                new_elves.insert(from, ());
+            }
+        }
+        self.elves = new_elves;
+        self.round += 1;
+        let first = match (self.moves[1].1, self.moves[1].2) {
+            (0, -1) => "north",
+            (0, 1) => "south",
+            (-1, 0) => "west",
+            _ => "east",
+        };
+        explain!(
+            "Round {}, looking {} first: {} of {} elves move",
+            self.round,
+            first,
+            n_moved,
+            self.elves.len()
+        );
+        // Update checks, Nothing always remains first
+        let first = self.moves.remove(1);
+        self.moves.push(first);
+        n_moved > 0
+    }
+    pub fn get_empty_ground_tiles(&self) -> i32 {
+        let Some(((min_x, min_y), (max_x, max_y))) = self.elves.bounds() else {
+            return 0;
+        };
+        (max_x - min_x + 1) * (max_y - min_y + 1) - self.elves.len() as i32
+    }
+    fn draw(&self) -> String {
+        self.elves
+            .render(|elf| if elf.is_some() { '#' } else { '.' })
+    }
+}
+pub fn parse(content: &str) -> Result<World, ParseError> {
+    let world = World::new(content)?;
+    info!("Start with {} elves", world.elves.len());
+    Ok(world)
+}
+
+common::params! {
+    pub struct Params {
+        /// Rounds before counting the empty ground in part 1
+        pub rounds: u32 = 10,
+    }
+}
+
+pub fn part1(world: &World, params: &Params) -> i32 {
+    let mut world = world.clone();
+    debug!("Start:\n{}", world.draw());
+    debug!("  = {}", world.get_empty_ground_tiles());
+    for i in 0..params.rounds {
+        world.round();
+        debug!("Round {}, {} elves", i, world.elves.len());
+        trace!("\n{}", world.draw());
+        debug!("  = {}", world.get_empty_ground_tiles());
+    }
+    if let Some(((min_x, min_y), (max_x, max_y))) = world.elves.bounds() {
+        let (width, height) = (max_x - min_x + 1, max_y - min_y + 1);
+        explain!(
+            "After {} rounds: {} * {} tiles - {} elves = {}",
+            params.rounds,
+            width,
+            height,
+            world.elves.len(),
+            world.get_empty_ground_tiles()
+        );
+    }
+    world.get_empty_ground_tiles()
+}
+
+pub fn part2(world: &World, _params: &Params) -> i32 {
+    let mut world = world.clone();
+    let mut i = 0;
+    loop {
+        i += 1;
+        if !world.round() {
+            break;
+        };
+    }
+    i
+}
+
+/// The elves spreading one round at a time, for the rounds of part 1 or
+/// until none moves in part 2.
+pub struct ElvesSimulation {
+    world: World,
+    round: u32,
+    last_round: Option<u32>,
+    moved: bool,
+}
+
+impl Simulation for ElvesSimulation {
+    fn step(&mut self) -> bool {
+        if !self.moved || Some(self.round) == self.last_round {
+            return false;
+        }
+        self.moved = self.world.round();
+        self.round += 1;
+        true
+    }
+    fn draw(&self) -> String {
+        self.world.draw()
+    }
+    fn status(&self) -> String {
+        format!(
+            "Round {}  Empty ground: {}{}",
+            self.round,
+            self.world.get_empty_ground_tiles(),
+            if self.moved { "" } else { "  No elf moved" }
+        )
+    }
+    fn image(&self) -> Option<Image> {
+        Some(
+            self.world
+                .elves
+                .to_grid(|elf| elf.is_some())
+                .bitmap(|&elf| elf),
+        )
+    }
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = World;
+    type Params = Params;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(content: &str) -> Result<World, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &World, params: &Params) -> i32 {
+        part1(input, params)
+    }
+
+    fn part2(input: &World, params: &Params) -> i32 {
+        part2(input, params)
+    }
+
+    fn simulation(input: &World, params: &Params, part: u8) -> Option<Box<dyn Simulation>> {
+        Some(Box::new(ElvesSimulation {
+            world: input.clone(),
+            round: 0,
+            last_round: (part == 1).then_some(params.rounds),
+            moved: true,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap(), &Params::default()), 110);
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap(), &Params::default()), 20);
+    }
+}
@@ -0,0 +1,404 @@
+use common::{budget::Abort, counters, explain, lines, Image, ParseError, Simulation, Token};
+use grid::Grid;
+use log::{debug, info, trace};
+use num::Integer;
+use search::Problem;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dir {
+    North,
+    South,
+    East,
+    West,
+}
+
+#[derive(Clone)]
+pub struct World {
+    pub width: i32,
+    pub height: i32,
+    /// Blizzards at minute 0
+    pub winds: Vec<(i32, i32, Dir)>,
+    /// Direction of the blizzard of each cell at minute 0
+    pub blizzards: Grid<Option<Dir>>,
+    pub start_x: i32,
+    pub end_x: i32,
+    /// Minutes after which the blizzards are back to their start
+    pub wrap_time: i32,
+}
+impl World {
+    pub fn new(content: &str) -> Result<World, ParseError> {
+        let lines = lines(content).collect::<Vec<_>>();
+        if lines.len() < 3 {
+            let n_line = lines.len();
+            return Err(ParseError::new(
+                n_line + 1,
+                1,
+                "a valley of at least 3 lines",
+                "",
+            ));
+        }
+        let width = lines[0].text.len() as i32 - 2;
+        let height = lines.len() as i32 - 2;
+        if width < 1 {
+            return Err(lines[0].error("a wall around a valley"));
+        }
+        // Position of the only opening in a wall line
+        let opening = |line: &Token| -> Result<i32, ParseError> {
+            let wall = format!("a wall of {} `#` with a single `.`", width + 2);
+            if line.text.len() as i32 != width + 2 {
+                return Err(line.error(&wall));
+            }
+            let openings = line.chars().filter(|c| c.char() != '#').collect::<Vec<_>>();
+            match openings[..] {
+                [c] if c.char() == '.' && c.column > 1 && c.column < line.text.len() => {
+                    Ok(c.column as i32 - 2)
+                }
+                [c, ..] => Err(c.error(&wall)),
+                [] => Err(line.error(&wall)),
+            }
+        };
+        let start_x = opening(&lines[0])?;
+        let end_x = opening(&lines[lines.len() - 1])?;
+        let mut start_winds = vec![];
+        for (y, line) in lines[1..lines.len() - 1].iter().enumerate() {
+            let y = y as i32;
+            if line.text.len() as i32 != width + 2 {
+                return Err(line.error(&format!("a line of {} characters", width + 2)));
+            }
+            for (x, c) in line.chars().enumerate() {
+                let x = x as i32 - 1;
+                if x == -1 || x == width {
+                    c.expect("#")?;
+                    continue;
+                }
+                match c.char() {
+                    '<' => start_winds.push((x, y, Dir::West)),
+                    '>' => start_winds.push((x, y, Dir::East)),
+                    '^' => start_winds.push((x, y, Dir::North)),
+                    'v' => start_winds.push((x, y, Dir::South)),
+                    '.' => (),
+                    _ => return Err(c.error("`.`, `<`, `>`, `^` or `v`")),
+                }
+            }
+        }
+        let mut blizzards = Grid::new(width as usize, height as usize, None);
+        for &(x, y, dir) in start_winds.iter() {
+            blizzards[(x, y)] = Some(dir);
+        }
+        Ok(World {
+            width,
+            height,
+            winds: start_winds,
+            blizzards,
+            start_x,
+            end_x,
+            wrap_time: width.lcm(&height),
+        })
+    }
+    /// Blizzards at a given minute
+    pub fn winds(&self, time: i32) -> Vec<(i32, i32, Dir)> {
+        self.winds
+            .iter()
+            .map(|&(x, y, dir)| match dir {
+                Dir::West => ((x - time).rem_euclid(self.width), y, dir),
+                Dir::East => ((x + time).rem_euclid(self.width), y, dir),
+                Dir::North => (x, (y - time).rem_euclid(self.height), dir),
+                Dir::South => (x, (y + time).rem_euclid(self.height), dir),
+            })
+            .collect()
+    }
+    pub fn is_blocked(&self, time: i32, x: i32, y: i32) -> bool {
+        let ok = if y == self.height {
+            x != self.end_x
+        } else if y == -1 {
+            x != self.start_x
+        } else if x < 0 || x >= self.width || y < 0 || y >= self.height {
+            // Outside of the valley is a wall
+            true
+        } else {
+            // The blizzards that can be there started on its row or column,
+            // `time` cells before it in their direction
+            let (w, h) = (time % self.width, time % self.height);
+            let blizzard = |x: i32, y: i32, dir| {
+                let (x, y) = (x.rem_euclid(self.width), y.rem_euclid(self.height));
+                self.blizzards[(x, y)] == Some(dir)
+            };
+            blizzard(x + w, y, Dir::West)
+                || blizzard(x - w, y, Dir::East)
+                || blizzard(x, y + h, Dir::North)
+                || blizzard(x, y - h, Dir::South)
+        };
+        trace!("Is blocked at {} {},{} -> {}", time, x, y, ok);
+        ok
+    }
+}
+
+/// Minutes of the crossings, None when their search ran out of budget before
+/// finding them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Minutes(pub Option<i32>);
+
+impl fmt::Display for Minutes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(minutes) => write!(f, "{}", minutes),
+            None => write!(f, "no answer"),
+        }
+    }
+}
+
+impl World {
+    pub fn total_time(&self, n_iter: u32) -> Minutes {
+        match self.search(n_iter) {
+            Ok(states) => Minutes(Some(Self::last_time(&states))),
+            Err(Some(_)) => Minutes(None),
+            Err(None) => panic!("no way across the valley"),
+        }
+    }
+    /// Time of `n_iter` crossings, None when a crossing has no way or the
+    /// search ran out of budget.
+    pub fn try_total_time(&self, n_iter: u32) -> Option<i32> {
+        self.search(n_iter)
+            .ok()
+            .map(|states| Self::last_time(&states))
+    }
+    fn last_time(states: &[(i32, i32, i32)]) -> i32 {
+        states.last().map_or(0, |&(time, _, _)| time)
+    }
+    /// States ( time, x, y ) of `n_iter` crossings back and forth, minute by
+    /// minute, None when a crossing has no way
+    pub fn crossings(&self, n_iter: u32) -> Option<Vec<(i32, i32, i32)>> {
+        self.search(n_iter).ok()
+    }
+    // The states of the crossings, or else why the search ran out of budget,
+    // None when a crossing has no way
+    fn search(&self, n_iter: u32) -> Result<Vec<(i32, i32, i32)>, Option<Abort>> {
+        let mut total_time = 0;
+        let mut states = vec![(0, self.start_x, -1)];
+        for i in 0..n_iter {
+            let (start_x, start_y) = if i.is_even() {
+                (self.start_x, -1)
+            } else {
+                (self.end_x, self.height)
+            };
+            let end = if i.is_even() {
+                (self.end_x, self.height)
+            } else {
+                (self.start_x, -1)
+            };
+            debug!(
+                "LOOP {} Start ({},{}) End {:?} Start Time {}",
+                i, start_x, start_y, end, total_time
+            );
+            let crossing = Crossing { world: self, end };
+            let (path, stats) = search::astar(&crossing, [(total_time, start_x, start_y)]);
+            counters::add("count", stats.iterations);
+            let Some(path) = path else {
+                if let (Some(abort), Some(bound)) = (stats.aborted, stats.bound) {
+                    // The crossings left take at least the walk from end to end
+                    let walk = (self.end_x - self.start_x).abs() + self.height + 1;
+                    let bound = total_time + bound as i32 + walk * (n_iter - i - 1) as i32;
+                    info!(
+                        "Stopped by {}, no answer, at least {} minutes",
+                        abort, bound
+                    );
+                }
+                return Err(stats.aborted);
+            };
+            trace!("Path: {:?}", path.states);
+            info!("Best {} in {} iterations", path.cost, stats.iterations);
+            let waits = path
+                .states
+                .windows(2)
+                .filter(|w| (w[0].1, w[0].2) == (w[1].1, w[1].2))
+                .count();
+            explain!(
+                "Crossing {} from {:?} to {:?} at minute {}: {} minutes, {} waiting, arriving at minute {}",
+                i + 1,
+                (start_x, start_y),
+                end,
+                total_time,
+                path.cost,
+                waits,
+                total_time + path.cost as i32
+            );
+            total_time += path.cost as i32;
+            states.extend_from_slice(&path.states[1..]);
+        }
+        Ok(states)
+    }
+    /// The valley at a given minute, with the number of blizzards where
+    /// several are, and the expedition `E`
+    pub fn draw(&self, time: i32, expedition: Option<(i32, i32)>) -> String {
+        self.cells(time, expedition).render(|&c| c)
+    }
+    /// Image of the valley at a given minute: gray walls, blizzards in blue,
+    /// brighter where several are, and the expedition in red
+    pub fn image(&self, time: i32, expedition: Option<(i32, i32)>) -> Image {
+        self.cells(time, expedition).pixmap(|&c| match c {
+            '#' => [128, 128, 128],
+            '.' => [0, 0, 0],
+            'E' => [230, 40, 40],
+            '2'..='9' => {
+                let n = c as u8 - b'0';
+                [25 * n, 25 * n, 255]
+            }
+            _ => [30, 30, 200],
+        })
+    }
+    fn cells(&self, time: i32, expedition: Option<(i32, i32)>) -> Grid<char> {
+        let mut grid = Grid::new(self.width as usize + 2, self.height as usize + 2, '.');
+        for x in 0..self.width + 2 {
+            for y in [0, self.height + 1] {
+                grid[(x, y)] = '#';
+            }
+        }
+        for y in 0..self.height + 2 {
+            grid[(0, y)] = '#';
+            grid[(self.width + 1, y)] = '#';
+        }
+        grid[(self.start_x + 1, 0)] = '.';
+        grid[(self.end_x + 1, self.height + 1)] = '.';
+        for (x, y, dir) in self.winds(time) {
+            let cell = &mut grid[(x + 1, y + 1)];
+            *cell = match (*cell, dir) {
+                ('.', Dir::North) => '^',
+                ('.', Dir::South) => 'v',
+                ('.', Dir::East) => '>',
+                ('.', Dir::West) => '<',
+                ('2'..='8', _) => (*cell as u8 + 1) as char,
+                _ => '2',
+            };
+        }
+        if let Some((x, y)) = expedition {
+            grid[(x + 1, y + 1)] = 'E';
+        }
+        grid
+    }
+}
+
+/// The expedition along its fastest crossings, minute by minute.
+pub struct ValleySimulation {
+    world: World,
+    states: Vec<(i32, i32, i32)>,
+    minute: usize,
+}
+
+impl Simulation for ValleySimulation {
+    fn step(&mut self) -> bool {
+        if self.minute + 1 >= self.states.len() {
+            return false;
+        }
+        self.minute += 1;
+        true
+    }
+    fn draw(&self) -> String {
+        let expedition = self.states.get(self.minute).map(|&(_, x, y)| (x, y));
+        self.world.draw(self.minute as i32, expedition)
+    }
+    fn status(&self) -> String {
+        match self.states.len() {
+            0 => String::from("No way across the valley"),
+            n => format!("Minute {}/{}", self.minute, n - 1),
+        }
+    }
+    fn image(&self) -> Option<Image> {
+        let expedition = self.states.get(self.minute).map(|&(_, x, y)| (x, y));
+        Some(self.world.image(self.minute as i32, expedition))
+    }
+}
+
+// One crossing of the valley, states are (time, x, y)
+struct Crossing<'a> {
+    world: &'a World,
+    end: (i32, i32),
+}
+
+impl Problem for Crossing<'_> {
+    type State = (i32, i32, i32);
+    type Key = (i32, i32, i32);
+
+    fn successors(&self, &(time, x, y): &Self::State) -> Vec<(Self::State, u64)> {
+        [(0, 0), (-1, 0), (0, -1), (1, 0), (0, 1)]
+            .iter()
+            .map(|(dx, dy)| (time + 1, x + dx, y + dy))
+            .filter(|&(t, x, y)| !self.world.is_blocked(t, x, y))
+            .map(|next| (next, 1))
+            .collect()
+    }
+    fn is_goal(&self, &(_, x, y): &Self::State) -> bool {
+        (x, y) == self.end
+    }
+    // The winds come back to the same place every wrap time
+    fn key(&self, &(time, x, y): &Self::State) -> Self::Key {
+        (time % self.world.wrap_time, x, y)
+    }
+    fn heuristic(&self, &(_, x, y): &Self::State) -> u64 {
+        ((x - self.end.0).abs() + (y - self.end.1).abs()) as u64
+    }
+}
+
+pub fn parse(content: &str) -> Result<World, ParseError> {
+    let world = World::new(content)?;
+    info!(
+        "World:  {}x{} Start:{}  End:{}, Wrap: {}",
+        world.width, world.height, world.start_x, world.end_x, world.wrap_time
+    );
+    Ok(world)
+}
+
+pub fn part1(world: &World) -> Minutes {
+    world.total_time(1)
+}
+
+pub fn part2(world: &World) -> Minutes {
+    world.total_time(3)
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = World;
+    type Params = ();
+    type Answer1 = Minutes;
+    type Answer2 = Minutes;
+
+    fn parse(content: &str) -> Result<World, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &World, _: &()) -> Minutes {
+        part1(input)
+    }
+
+    fn part2(input: &World, _: &()) -> Minutes {
+        part2(input)
+    }
+
+    fn simulation(input: &World, _: &(), part: u8) -> Option<Box<dyn Simulation>> {
+        let n_iter = if part == 1 { 1 } else { 3 };
+        Some(Box::new(ValleySimulation {
+            world: input.clone(),
+            states: input.crossings(n_iter).unwrap_or_default(),
+            minute: 0,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), Minutes(Some(18)));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), Minutes(Some(54)));
+    }
+}
@@ -0,0 +1,150 @@
+use common::{checked, explain, lines, ParseError};
+use log::info;
+
+pub fn snafu_to_i64(s: &str) -> i64 {
+    let mut v = 0i64;
+    for (i, c) in s.chars().rev().enumerate() {
+        let cv = match c {
+            '2' => 2,
+            '1' => 1,
+            '0' => 0,
+            '-' => -1,
+            '=' => -2,
+            _ => panic!(),
+        };
+        let context = || format!("digit {} from the right of {}", i + 1, s);
+        let place = checked::pow(5i64, i as u32, context);
+        v = checked::add(v, checked::mul(cv, place, context), context);
+    }
+    v
+}
+pub fn i64_to_snafu(v: i64) -> String {
+    let mut s = vec![];
+    let mut v = v;
+    assert!(v > 0);
+    while v > 0 {
+        v = checked::add(v, 2, || String::from("conversion to SNAFU"));
+
+        s.insert(
+            0,
+            match v % 5 {
+                0 => String::from("="),
+                1 => String::from("-"),
+                2 => String::from("0"),
+                3 => String::from("1"),
+                4 => String::from("2"),
+                _ => panic!(),
+            },
+        );
+        v /= 5;
+    }
+    s.into_iter().collect::<String>()
+}
+
+pub fn parse(content: &str) -> Result<Vec<String>, ParseError> {
+    lines(content)
+        .map(|line| {
+            if line.is_empty() {
+                return Err(line.error("a SNAFU number"));
+            }
+            if let Some(c) = line.chars().find(|c| !"210-=".contains(c.char())) {
+                return Err(c.error("a SNAFU digit, `2`, `1`, `0`, `-` or `=`"));
+            }
+            Ok(line.text.to_string())
+        })
+        .collect()
+}
+
+pub fn part1(lines: &[String]) -> String {
+    let mut total = 0;
+    for line in lines.iter() {
+        let context = || String::from("sum of the numbers");
+        let value = snafu_to_i64(line);
+        total = checked::add(total, value, context);
+        explain!("{} = {}, total {}", line, value, total);
+    }
+    info!("Total: {} -> {}", total, i64_to_snafu(total));
+    let snafu = i64_to_snafu(total);
+    explain!("{} in SNAFU: {}", total, snafu);
+    snafu
+}
+
+// There is no second puzzle on the last day, both parts give the fuel sum.
+pub fn part2(lines: &[String]) -> String {
+    part1(lines)
+}
+
+pub struct Solution;
+
+impl common::Solver for Solution {
+    type Input = Vec<String>;
+    type Params = ();
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(content: &str) -> Result<Vec<String>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Vec<String>, _: &()) -> String {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<String>, _: &()) -> String {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    // Decimal/SNAFU pairs from the puzzle statement
+    const SNAFU: [(i64, &str); 15] = [
+        (1, "1"),
+        (2, "2"),
+        (3, "1="),
+        (4, "1-"),
+        (5, "10"),
+        (6, "11"),
+        (7, "12"),
+        (8, "2="),
+        (9, "2-"),
+        (10, "20"),
+        (15, "1=0"),
+        (20, "1-0"),
+        (2022, "1=11-2"),
+        (12345, "1-0---0"),
+        (314159265, "1121-1110-1=0"),
+    ];
+
+    #[test]
+    fn snafu_round_trip() {
+        for (v, s) in SNAFU {
+            assert_eq!(snafu_to_i64(s), v);
+            assert_eq!(i64_to_snafu(v), s);
+        }
+    }
+
+    #[test]
+    fn checked_overflow() {
+        let lines = parse(&format!("1{}\n", "0".repeat(28))).unwrap();
+        checked::set(true);
+        let overflow = checked::catch(|| part1(&lines)).unwrap_err();
+        checked::set(false);
+        assert_eq!(overflow.operation, "5 ^ 28");
+        assert!(overflow.context.starts_with("digit 29 from the right"));
+    }
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), "2=-1=0");
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), "2=-1=0");
+    }
+}
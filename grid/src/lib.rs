@@ -0,0 +1,403 @@
+//! 2D maps of the puzzles: a dense `Grid` for bounded maps, and a `SparseGrid`
+//! for maps that grow in every direction.
+//!
+//! Positions are `(x, y)`, with x going right and y going down, as in the
+//! puzzle texts.
+use std::{
+    collections::{hash_map, HashMap},
+    ops::{Index, IndexMut},
+};
+
+use common::{lines, Image, ParseError};
+
+pub type Pos = (i32, i32);
+
+/// Offsets of the 4 orthogonal neighbours: up, right, down, left.
+pub const NEIGHBOURS4: [Pos; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+/// Offsets of the 8 neighbours, diagonals included, clockwise from up-left.
+pub const NEIGHBOURS8: [Pos; 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// Dense rectangular grid, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+    /// None if the rows do not all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        if rows.iter().any(|r| r.len() != width) {
+            return None;
+        }
+        Some(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+    /// Parse a map with one character per cell, `cell` returns None for the
+    /// characters that are not `expected`. All the lines must have the same length.
+    pub fn parse<F>(content: &str, expected: &str, cell: F) -> Result<Grid<T>, ParseError>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let mut rows: Vec<Vec<T>> = vec![];
+        for line in lines(content) {
+            let row = line
+                .chars()
+                .map(|c| cell(c.char()).ok_or_else(|| c.error(expected)))
+                .collect::<Result<Vec<_>, _>>()?;
+            if row.is_empty() || rows.first().is_some_and(|r| r.len() != row.len()) {
+                return Err(line.error("a row as wide as the first one"));
+            }
+            rows.push(row);
+        }
+        if rows.is_empty() {
+            return Err(ParseError::new(1, 1, "a map", ""));
+        }
+        Ok(Grid::from_rows(rows).unwrap())
+    }
+    /// Same as `parse`, for maps whose lines have different lengths: the
+    /// missing cells at the end of the short lines are set to `fill`.
+    pub fn parse_padded<F>(
+        content: &str,
+        fill: T,
+        expected: &str,
+        cell: F,
+    ) -> Result<Grid<T>, ParseError>
+    where
+        T: Clone,
+        F: Fn(char) -> Option<T>,
+    {
+        let mut rows = vec![];
+        for line in lines(content) {
+            let row = line
+                .chars()
+                .map(|c| cell(c.char()).ok_or_else(|| c.error(expected)))
+                .collect::<Result<Vec<_>, _>>()?;
+            rows.push(row);
+        }
+        let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        if width == 0 {
+            return Err(ParseError::new(1, 1, "a map", ""));
+        }
+        for row in rows.iter_mut() {
+            row.resize(width, fill.clone());
+        }
+        Ok(Grid::from_rows(rows).unwrap())
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.1 as usize * self.width + pos.0 as usize)
+        } else {
+            None
+        }
+    }
+    /// None outside of the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+    /// The grid repeats itself in every direction.
+    pub fn get_wrapping(&self, (x, y): Pos) -> &T {
+        let x = x.rem_euclid(self.width as i32);
+        let y = y.rem_euclid(self.height as i32);
+        &self[(x, y)]
+    }
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| ((i % width) as i32, (i / width) as i32))
+    }
+    /// All cells with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+    /// Position of the first cell, row by row, matching `f`.
+    pub fn position<F: Fn(&T) -> bool>(&self, f: F) -> Option<Pos> {
+        self.iter().find(|(_, t)| f(t)).map(|(pos, _)| pos)
+    }
+    /// The 4 orthogonal neighbours inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        offsets(pos, &NEIGHBOURS4).filter(|&p| self.contains(p))
+    }
+    /// The 8 neighbours inside the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        offsets(pos, &NEIGHBOURS8).filter(|&p| self.contains(p))
+    }
+    pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+    /// One line per row, `f` giving the character of each cell.
+    pub fn render<F: Fn(&T) -> char>(&self, f: F) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            s.extend(row.iter().map(&f));
+            s.push('\n');
+        }
+        s
+    }
+    /// Black and white image, one pixel per cell, `f` true for black.
+    pub fn bitmap<F: Fn(&T) -> bool>(&self, f: F) -> Image {
+        Image::bitmap(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+    /// Gray image, one pixel per cell, `f` giving its level from 0 ( black )
+    /// to `max` ( white ).
+    pub fn graymap<F: Fn(&T) -> u16>(&self, max: u16, f: F) -> Image {
+        let pixels = self.cells.iter().map(f).collect();
+        Image::graymap(self.width, self.height, max, pixels)
+    }
+    /// Color image, one pixel per cell, `f` giving its RGB color.
+    pub fn pixmap<F: Fn(&T) -> [u8; 3]>(&self, f: F) -> Image {
+        Image::pixmap(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+/// Panics outside of the grid, see `get` for checked access.
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+    fn index(&self, pos: Pos) -> &T {
+        match self.index_of(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "position {:?} outside of the {}x{} grid",
+                pos, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        match self.index_of(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "position {:?} outside of the {}x{} grid",
+                pos, self.width, self.height
+            ),
+        }
+    }
+}
+
+fn offsets(pos: Pos, offsets: &[Pos]) -> impl Iterator<Item = Pos> + '_ {
+    offsets.iter().map(move |(dx, dy)| (pos.0 + dx, pos.1 + dy))
+}
+
+/// Unbounded grid, only storing the set cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+    /// Parse a map with one character per cell, `cell` returns `Ok(None)` for
+    /// the empty cells and an error for the characters that are not `expected`.
+    pub fn parse<F>(content: &str, expected: &str, cell: F) -> Result<SparseGrid<T>, ParseError>
+    where
+        F: Fn(char) -> Result<Option<T>, ()>,
+    {
+        let mut grid = SparseGrid::new();
+        for (y, line) in lines(content).enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(t) = cell(c.char()).map_err(|_| c.error(expected))? {
+                    grid.insert((x as i32, y as i32), t);
+                }
+            }
+        }
+        Ok(grid)
+    }
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.cells.contains_key(&pos)
+    }
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+    pub fn insert(&mut self, pos: Pos, t: T) -> Option<T> {
+        self.cells.insert(pos, t)
+    }
+    pub fn remove(&mut self, pos: Pos) -> Option<T> {
+        self.cells.remove(&pos)
+    }
+    /// Set cells, in no particular order.
+    pub fn iter(&self) -> hash_map::Iter<'_, Pos, T> {
+        self.cells.iter()
+    }
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.cells.keys().copied()
+    }
+    /// Smallest rectangle containing all the set cells, as (min, max) corners.
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        let mut positions = self.positions();
+        let first = positions.next()?;
+        Some(positions.fold((first, first), |(min, max), (x, y)| {
+            ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+        }))
+    }
+    /// The 4 orthogonal neighbour positions, set or not.
+    pub fn neighbours4(pos: Pos) -> impl Iterator<Item = Pos> {
+        NEIGHBOURS4
+            .into_iter()
+            .map(move |(dx, dy)| (pos.0 + dx, pos.1 + dy))
+    }
+    /// The 8 neighbour positions, set or not.
+    pub fn neighbours8(pos: Pos) -> impl Iterator<Item = Pos> {
+        NEIGHBOURS8
+            .into_iter()
+            .map(move |(dx, dy)| (pos.0 + dx, pos.1 + dy))
+    }
+    /// Rendering of the `bounds`, one line per row, `f` giving the character
+    /// of each cell, set or not.
+    pub fn render<F: Fn(Option<&T>) -> char>(&self, f: F) -> String {
+        let mut s = String::new();
+        if let Some((min, max)) = self.bounds() {
+            for y in min.1..=max.1 {
+                s.extend((min.0..=max.0).map(|x| f(self.get((x, y)))));
+                s.push('\n');
+            }
+        }
+        s
+    }
+    /// Dense grid of the `bounds`, `f` giving each cell from the cell here,
+    /// set or not. Empty without any set cell.
+    pub fn to_grid<U, F: Fn(Option<&T>) -> U>(&self, f: F) -> Grid<U> {
+        let Some((min, max)) = self.bounds() else {
+            return Grid::from_rows(vec![]).unwrap();
+        };
+        let rows = (min.1..=max.1)
+            .map(|y| (min.0..=max.0).map(|x| f(self.get((x, y)))).collect())
+            .collect();
+        Grid::from_rows(rows).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#.#\n..#\n";
+
+    #[test]
+    fn dense() {
+        let mut grid = Grid::parse(MAP, "`#` or `.`", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((2, 1)), Some(&true));
+        assert_eq!(grid.get((3, 1)), None);
+        assert_eq!(grid.get((-1, 0)), None);
+        assert!(*grid.get_wrapping((-1, -1)));
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((0, 0)).count(), 3);
+        grid[(1, 1)] = true;
+        assert_eq!(grid.render(|&b| if b { '#' } else { '.' }), "#.#\n.##\n");
+        assert_eq!(grid.position(|&b| !b), Some((1, 0)));
+        assert_eq!(grid.bitmap(|&b| b).to_netpbm(), "P1\n3 2\n1 0 1 0 1 1\n");
+        assert_eq!(
+            grid.graymap(2, |&b| b as u16 * 2).to_netpbm(),
+            "P2\n3 2\n2\n2 0 2 0 2 2\n"
+        );
+    }
+
+    #[test]
+    fn dense_parse_errors() {
+        let cell = |c| if c == '.' { Some(()) } else { None };
+        let err = Grid::parse("..\n.x", "`.`", cell).unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 2, "x"));
+        let err = Grid::parse("..\n...", "`.`", cell).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let padded = Grid::parse_padded("..\n.", (), "`.`", cell).unwrap();
+        assert_eq!((padded.width(), padded.height()), (2, 2));
+    }
+
+    #[test]
+    fn sparse() {
+        let mut grid = SparseGrid::parse(MAP, "`#` or `.`", |c| match c {
+            '#' => Ok(Some(())),
+            '.' => Ok(None),
+            _ => Err(()),
+        })
+        .unwrap();
+        assert_eq!(grid.len(), 3);
+        grid.insert((-1, 3), ());
+        assert_eq!(grid.bounds(), Some(((-1, 0), (2, 3))));
+        assert_eq!(
+            grid.render(|c| if c.is_some() { '#' } else { '.' }),
+            ".#.#\n...#\n....\n#...\n"
+        );
+        assert_eq!(SparseGrid::<()>::neighbours8((0, 0)).count(), 8);
+        let dense = grid.to_grid(|c| c.is_some());
+        assert_eq!((dense.width(), dense.height()), (4, 4));
+        assert!(dense[(0, 3)]);
+        assert_eq!(SparseGrid::<()>::new().to_grid(|_| 0).width(), 0);
+    }
+}
@@ -0,0 +1,442 @@
+//! Python module `aoc2022` of the day solvers, built with maturin:
+//! `solve` and `run` a part on an input, `parse` an input once to solve its
+//! parts, and models of a few days: the day-16 `Volcano` with its `Valve`s,
+//! the day-19 `Blueprint`s, the day-13 `Packet`s and the day-25 SNAFU numbers.
+use std::time::Duration;
+
+use common::{
+    budget::{self, Budget},
+    checked, Error, Parts, Settings,
+};
+use day_13::Entry;
+use pyo3::{
+    exceptions::{PyOverflowError, PyValueError},
+    prelude::*,
+    pyclass::CompareOp,
+    types::{PyBool, PyDict, PyList},
+};
+
+// Parameters of a day from a dict, like `{"minutes1": 20}`
+fn settings(params: Option<&Bound<'_, PyDict>>) -> PyResult<Settings> {
+    let mut pairs = vec![];
+    for (name, value) in params.iter().flat_map(|params| params.iter()) {
+        let value = match value.is_instance_of::<PyBool>() {
+            true => value.extract::<bool>()?.to_string(),
+            false => value.str()?.to_string(),
+        };
+        pairs.push((name.str()?.to_string(), value));
+    }
+    Settings::from_pairs(pairs).map_err(PyValueError::new_err)
+}
+
+fn error(e: Error, input: &str) -> PyErr {
+    match e {
+        Error::Param(e) => PyValueError::new_err(e),
+        Error::Parse(e) => PyValueError::new_err(e.diagnostic(input)),
+    }
+}
+
+fn no_day(day: u32) -> PyErr {
+    PyValueError::new_err(format!("no day {}", day))
+}
+
+fn check_part(part: u8) -> PyResult<u8> {
+    match part {
+        1 | 2 => Ok(part),
+        _ => Err(PyValueError::new_err(format!("no part {}", part))),
+    }
+}
+
+/// Solves a part of a day, `params` setting the parameters of the day. The
+/// solvers run without the GIL, like every solve of the module.
+#[pyfunction]
+#[pyo3(signature = (day, part, input, params=None))]
+fn solve(
+    py: Python<'_>,
+    day: u32,
+    part: u8,
+    input: &str,
+    params: Option<&Bound<'_, PyDict>>,
+) -> PyResult<String> {
+    let part = check_part(part)?;
+    let settings = settings(params)?;
+    match py.detach(|| aoc::solve(day, part, input, &settings)) {
+        Some(result) => result.map_err(|e| error(e, input)),
+        None => Err(no_day(day)),
+    }
+}
+
+/// Solves parts of a day like `aoc run --format json`, one dict per part with
+/// the answer, the times and the counters. With a `timeout` in seconds, the
+/// searches of days 16, 19 and 24 stop with their best answer, not `proven`.
+#[pyfunction]
+#[pyo3(signature = (day, input, parts=vec![1, 2], params=None, timeout=None))]
+fn run<'py>(
+    py: Python<'py>,
+    day: u32,
+    input: &str,
+    parts: Vec<u8>,
+    params: Option<&Bound<'py, PyDict>>,
+    timeout: Option<f64>,
+) -> PyResult<Vec<Bound<'py, PyDict>>> {
+    let parts = parts
+        .into_iter()
+        .map(check_part)
+        .collect::<PyResult<Vec<_>>>()?;
+    let timeout = match timeout.map(Duration::try_from_secs_f64) {
+        Some(Ok(timeout)) => Some(timeout),
+        Some(Err(e)) => return Err(PyValueError::new_err(e.to_string())),
+        None => None,
+    };
+    let settings = settings(params)?;
+    // The budget is set on this thread, which the solvers keep without the GIL
+    let result = py.detach(|| {
+        budget::set(Budget {
+            timeout,
+            max_memory: None,
+        });
+        let result = aoc::run(day, &parts, input, &settings);
+        budget::set(Budget::default());
+        result
+    });
+    let runs = result
+        .ok_or_else(|| no_day(day))?
+        .map_err(|e| error(e, input))?;
+    let mut dicts = vec![];
+    for run in runs {
+        let dict = PyDict::new(py);
+        dict.set_item("day", run.day)?;
+        dict.set_item("part", run.part)?;
+        dict.set_item("answer", run.answer)?;
+        dict.set_item("proven", run.aborted.is_none())?;
+        dict.set_item("time_ms", run.time.as_secs_f64() * 1000.)?;
+        dict.set_item("parse_ms", run.parse_time.as_secs_f64() * 1000.)?;
+        let counters = PyDict::new(py);
+        for (name, value) in run.counters {
+            counters.set_item(name, value)?;
+        }
+        dict.set_item("counters", counters)?;
+        if let Some(abort) = run.aborted {
+            dict.set_item("aborted", abort.to_string())?;
+        }
+        dicts.push(dict);
+    }
+    Ok(dicts)
+}
+
+/// An input parsed once, to solve its parts without parsing it again.
+#[pyclass(frozen)]
+struct Input {
+    day: u32,
+    // Quoted by the errors of a part, like a day-22 map that is not a cube
+    input: String,
+    parts: Parts,
+}
+
+#[pymethods]
+impl Input {
+    fn solve(&self, py: Python<'_>, part: u8) -> PyResult<String> {
+        let part = check_part(part)?;
+        py.detach(|| (self.parts)(part))
+            .map_err(|e| error(e, &self.input))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<day {} input>", self.day)
+    }
+}
+
+/// Parses the input of a day, `params` setting the parameters of the day.
+#[pyfunction]
+#[pyo3(signature = (day, input, params=None))]
+fn parse(day: u32, input: &str, params: Option<&Bound<'_, PyDict>>) -> PyResult<Input> {
+    match aoc::prepare(day, input, &settings(params)?) {
+        Some(Ok(parts)) => Ok(Input {
+            day,
+            input: input.to_string(),
+            parts,
+        }),
+        Some(Err(e)) => Err(error(e, input)),
+        None => Err(no_day(day)),
+    }
+}
+
+/// A day-16 valve, with the valves its tunnels lead to.
+#[pyclass(get_all, frozen)]
+struct Valve {
+    name: String,
+    flow: u32,
+    tunnels: Vec<String>,
+}
+
+#[pymethods]
+impl Valve {
+    fn __repr__(&self) -> String {
+        format!(
+            "Valve({}, flow={}, tunnels=[{}])",
+            self.name,
+            self.flow,
+            self.tunnels.join(", ")
+        )
+    }
+}
+
+/// The day-16 graph of valves.
+#[pyclass(frozen)]
+struct Volcano(day_16::Volcano);
+
+#[pymethods]
+impl Volcano {
+    #[new]
+    fn new(input: &str) -> PyResult<Volcano> {
+        let volcano = day_16::parse(input).map_err(|e| error(Error::Parse(e), input))?;
+        Ok(Volcano(volcano))
+    }
+
+    /// Valves sorted by name.
+    #[getter]
+    fn valves(&self) -> Vec<Valve> {
+        let mut names = vec![String::new(); self.0.ids.len()];
+        for (name, &id) in self.0.ids.iter() {
+            names[id as usize] = name.clone();
+        }
+        let mut valves = self
+            .0
+            .valves
+            .iter()
+            .map(|(&id, valve)| Valve {
+                name: names[id as usize].clone(),
+                flow: valve.flow,
+                tunnels: valve
+                    .tunnels
+                    .iter()
+                    .map(|&t| names[t as usize].clone())
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        valves.sort_by(|a, b| a.name.cmp(&b.name));
+        valves
+    }
+
+    /// Most pressure released in `minutes` by 1 or 2 actors from `start`.
+    #[pyo3(signature = (minutes, actors=1, start="AA"))]
+    fn best_pressure(
+        &self,
+        py: Python<'_>,
+        minutes: u32,
+        actors: usize,
+        start: &str,
+    ) -> PyResult<u32> {
+        if !self.0.ids.contains_key(start) {
+            return Err(PyValueError::new_err(format!("no valve {}", start)));
+        }
+        if minutes == 0 || !(1..=2).contains(&actors) {
+            return Err(PyValueError::new_err(
+                "expecting 1 minute or more, 1 or 2 actors",
+            ));
+        }
+        Ok(py.detach(|| self.0.best_score(start, minutes, actors)))
+    }
+}
+
+/// A day-19 blueprint, the costs of its robots.
+#[pyclass(frozen)]
+struct Blueprint(day_19::Blueprint);
+
+#[pymethods]
+impl Blueprint {
+    /// Blueprints of a day-19 input.
+    #[staticmethod]
+    fn parse(input: &str) -> PyResult<Vec<Blueprint>> {
+        let blueprints = day_19::parse(input).map_err(|e| error(Error::Parse(e), input))?;
+        Ok(blueprints.into_iter().map(Blueprint).collect())
+    }
+
+    #[getter]
+    fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    /// Ore
+    #[getter]
+    fn ore_robot(&self) -> u32 {
+        self.0.ore_robot
+    }
+
+    /// Ore
+    #[getter]
+    fn clay_robot(&self) -> u32 {
+        self.0.clay_robot
+    }
+
+    /// Ore and clay
+    #[getter]
+    fn obsidian_robot(&self) -> (u32, u32) {
+        self.0.obsidian_robot
+    }
+
+    /// Ore and obsidian
+    #[getter]
+    fn geode_robot(&self) -> (u32, u32) {
+        self.0.geode_robot
+    }
+
+    /// Most geodes opened in `minutes`.
+    fn best_geodes(&self, py: Python<'_>, minutes: u32) -> u32 {
+        py.detach(|| day_19::best_geodes(&self.0, minutes))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<blueprint {}>", self.0.id)
+    }
+}
+
+// Text of a packet, like `[1,[2,3]]`
+fn packet_list<'py>(py: Python<'py>, entry: &Entry) -> PyResult<Bound<'py, PyAny>> {
+    match entry {
+        Entry::Value(v) => Ok(v.into_pyobject(py)?.into_any()),
+        Entry::List(entries) => {
+            let entries = entries
+                .iter()
+                .map(|entry| packet_list(py, entry))
+                .collect::<PyResult<Vec<_>>>()?;
+            Ok(PyList::new(py, entries)?.into_any())
+        }
+    }
+}
+
+/// A day-13 packet, compared by the rules of the distress signal.
+#[pyclass(frozen)]
+struct Packet(Entry);
+
+#[pymethods]
+impl Packet {
+    #[new]
+    fn new(text: &str) -> PyResult<Packet> {
+        match common::lines(text).collect::<Vec<_>>()[..] {
+            [line] => Entry::parse(line)
+                .map(Packet)
+                .map_err(|e| error(Error::Parse(e), text)),
+            _ => Err(PyValueError::new_err("expecting a single packet")),
+        }
+    }
+
+    /// The packet as nested lists of ints.
+    fn to_list<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        packet_list(py, &self.0)
+    }
+
+    fn __richcmp__(&self, other: &Packet, op: CompareOp) -> bool {
+        op.matches(self.0.cmp(&other.0))
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Packet('{}')", self.0)
+    }
+}
+
+// Runs `f` in checked mode, an overflow raising an OverflowError
+fn checked<T>(f: impl FnOnce() -> T) -> PyResult<T> {
+    let enabled = checked::enabled();
+    checked::set(true);
+    let result = checked::catch(f);
+    checked::set(enabled);
+    result.map_err(|overflow| PyOverflowError::new_err(overflow.to_string()))
+}
+
+/// Value of a SNAFU number, like `2=-01` for 976.
+#[pyfunction]
+fn from_snafu(snafu: &str) -> PyResult<i64> {
+    if snafu.is_empty() || !snafu.chars().all(|c| "=-012".contains(c)) {
+        let message = format!("expecting digits =, -, 0, 1 and 2, not {:?}", snafu);
+        return Err(PyValueError::new_err(message));
+    }
+    checked(|| day_25::snafu_to_i64(snafu))
+}
+
+/// SNAFU number of a positive value.
+#[pyfunction]
+fn to_snafu(value: i64) -> PyResult<String> {
+    if value <= 0 {
+        return Err(PyValueError::new_err("SNAFU numbers are positive"));
+    }
+    checked(|| day_25::i64_to_snafu(value))
+}
+
+#[pymodule]
+fn aoc2022(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(run, m)?)?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(from_snafu, m)?)?;
+    m.add_function(wrap_pyfunction!(to_snafu, m)?)?;
+    m.add_class::<Input>()?;
+    m.add_class::<Valve>()?;
+    m.add_class::<Volcano>()?;
+    m.add_class::<Blueprint>()?;
+    m.add_class::<Packet>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+
+    use super::*;
+
+    #[test]
+    fn module() {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "aoc2022").unwrap();
+            aoc2022(&module).unwrap();
+            let locals = PyDict::new(py);
+            locals.set_item("aoc", module).unwrap();
+            let examples = [
+                ("day_15", include_str!("../../day-15/example.txt")),
+                ("day_16", include_str!("../../day-16/example.txt")),
+                ("day_19", include_str!("../../day-19/example.txt")),
+            ];
+            for (name, example) in examples {
+                locals.set_item(name, example).unwrap();
+            }
+            let script = CString::new(
+                r#"
+assert aoc.solve(15, 1, day_15, {"row": 10}) == "26"
+input = aoc.parse(16, day_16)
+assert [input.solve(1), input.solve(2)] == ["1651", "1707"]
+runs = aoc.run(16, day_16, parts=[2], timeout=0)
+assert not runs[0]["proven"] and runs[0]["aborted"] == "timeout"
+assert runs[0]["counters"]["iterations"] > 0
+try:
+    aoc.solve(16, 1, "Valve AA")
+    assert False
+except ValueError as e:
+    assert "expected" in str(e)
+
+volcano = aoc.Volcano(day_16)
+assert volcano.valves[0].name == "AA" and volcano.valves[0].tunnels == ["DD", "II", "BB"]
+assert volcano.best_pressure(30) == 1651
+blueprints = aoc.Blueprint.parse(day_19)
+assert blueprints[1].geode_robot == (3, 12)
+assert blueprints[0].best_geodes(24) == 9
+
+assert aoc.Packet("[[1],[2,3,4]]") < aoc.Packet("[[1],4]")
+assert sorted([aoc.Packet("[2]"), aoc.Packet("[]")])[0].to_list() == []
+assert aoc.to_snafu(976) == "2=-01" and aoc.from_snafu("2=-01") == 976
+try:
+    aoc.from_snafu("2" * 28)
+    assert False
+except OverflowError as e:
+    assert "out of i64" in str(e)
+"#,
+            )
+            .unwrap();
+            py.run(&script, None, Some(&locals)).unwrap();
+        });
+    }
+}
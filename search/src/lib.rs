@@ -0,0 +1,672 @@
+//! State space searches of the puzzles.
+//!
+//! Shortest paths ( `bfs`, `dijkstra`, `astar` ) explore a `Problem`, and
+//! return the path to the first goal reached along with its cost.
+//! Best scores ( `branch_and_bound` ) explore a `Maximize` problem, cutting the
+//! states whose upper bound cannot beat the best score found so far.
+//!
+//! All of them stop early past the budget of the part ( `common::budget` ),
+//! with the best score found so far, or a lower bound of the cheapest cost.
+//! `branch_and_bound` also saves its state to the checkpoint of the part, if
+//! any ( `common::checkpoint` ), and continues from it when resumed.
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    num::NonZeroUsize,
+};
+
+use common::{
+    budget::{self, Abort},
+    checkpoint::{self, Record, Saved},
+};
+use log::{debug, warn};
+use lru::LruCache;
+
+/// A shortest path problem.
+pub trait Problem {
+    type State: Clone;
+    type Key: Hash + Eq;
+
+    /// States reachable in one move from `state`, with the cost of the move.
+    fn successors(&self, state: &Self::State) -> Vec<(Self::State, u64)>;
+    fn is_goal(&self, state: &Self::State) -> bool;
+    /// States sharing a key are explored once.
+    fn key(&self, state: &Self::State) -> Self::Key;
+    /// Lower bound of the cost from `state` to a goal, only used by `astar`.
+    fn heuristic(&self, _state: &Self::State) -> u64 {
+        0
+    }
+}
+
+/// A best score problem.
+pub trait Maximize {
+    type State: Clone;
+    type Score: Ord + Copy;
+    type Key: Hash + Eq;
+    /// What is kept of an explored state to compare it with the next ones of
+    /// the same key.
+    type Mark;
+    /// Explore the states of highest `priority` first instead of depth first.
+    const BEST_FIRST: bool = false;
+    /// Most keys kept to deduplicate the states, the least recently seen ones
+    /// are forgotten past it.
+    const MAX_SEEN: usize = 5_000_000;
+
+    /// Pushes the states reachable in one move from `state` to `next`, a
+    /// buffer reused from state to state.
+    fn successors(&self, state: &Self::State, next: &mut Vec<Self::State>);
+    /// Score `state` is sure to reach.
+    fn score(&self, state: &Self::State) -> Self::Score;
+    /// Score that no state reachable from `state` can exceed.
+    fn upper_bound(&self, state: &Self::State) -> Self::Score;
+    fn priority(&self, _state: &Self::State) -> u64 {
+        0
+    }
+    /// Key and mark of a state worth deduplicating, None to always explore it.
+    fn dedup(&self, _state: &Self::State) -> Option<(Self::Key, Self::Mark)> {
+        None
+    }
+    /// Whether a state marked `mark` is useless once a state of the same key
+    /// marked `seen` is explored. By default any state of the same key is.
+    fn dominates(&self, _seen: &Self::Mark, _mark: &Self::Mark) -> bool {
+        true
+    }
+}
+
+/// States from a start to a goal, both included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S> {
+    pub states: Vec<S>,
+    pub cost: u64,
+}
+
+/// Best score of a `Maximize` problem, with the state reaching it.
+pub type Best<P> = Option<(<P as Maximize>::Score, <P as Maximize>::State)>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Expanded states.
+    pub iterations: u64,
+    /// States cut by their upper bound.
+    pub pruned: u64,
+    /// Size of the deduplication table.
+    pub seen: u64,
+    /// Why the search stopped early, its result is then unproven.
+    pub aborted: Option<Abort>,
+    /// Lower bound of the cost of a path, when the search stopped early.
+    pub bound: Option<u64>,
+}
+
+// Counts only, a resumed search is not aborted
+impl Record for Stats {
+    fn record(&self, numbers: &mut Vec<u64>) {
+        (self.iterations, self.pruned, self.seen).record(numbers);
+    }
+    fn restore(numbers: &mut impl Iterator<Item = u64>) -> Option<Stats> {
+        let (iterations, pruned, seen) = Record::restore(numbers)?;
+        Some(Stats {
+            iterations,
+            pruned,
+            seen,
+            ..Stats::default()
+        })
+    }
+}
+
+impl Stats {
+    fn iterate(&mut self) {
+        self.iterations += 1;
+        if self.iterations.is_multiple_of(1_000_000) {
+            debug!(
+                "{}M iterations, {}K pruned, {}K seen",
+                self.iterations / 1_000_000,
+                self.pruned / 1000,
+                self.seen / 1000
+            );
+        }
+    }
+    // Checked every 1024 iterations, to keep the searches quick
+    fn out_of_budget(&mut self) -> bool {
+        if self.iterations.is_multiple_of(1024) && self.aborted.is_none() {
+            self.aborted = budget::exceeded();
+            if let Some(abort) = self.aborted {
+                debug!("Stopped after {} iterations: {}", self.iterations, abort);
+            }
+        }
+        self.aborted.is_some()
+    }
+}
+
+/// Explored states with the index of their parent, to rebuild the paths.
+struct Tree<S> {
+    nodes: Vec<(S, Option<usize>)>,
+}
+
+impl<S: Clone> Tree<S> {
+    fn new() -> Tree<S> {
+        Tree { nodes: vec![] }
+    }
+    fn add(&mut self, state: S, parent: Option<usize>) -> usize {
+        self.nodes.push((state, parent));
+        self.nodes.len() - 1
+    }
+    fn path(&self, mut id: usize, cost: u64) -> Path<S> {
+        let mut states = vec![self.nodes[id].0.clone()];
+        while let Some(parent) = self.nodes[id].1 {
+            states.push(self.nodes[parent].0.clone());
+            id = parent;
+        }
+        states.reverse();
+        Path { states, cost }
+    }
+}
+
+/// Shortest path in number of moves, the move costs are ignored.
+pub fn bfs<P, I>(problem: &P, starts: I) -> (Option<Path<P::State>>, Stats)
+where
+    P: Problem,
+    I: IntoIterator<Item = P::State>,
+{
+    let mut stats = Stats::default();
+    let mut tree = Tree::new();
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if seen.insert(problem.key(&start)) {
+            queue.push_back((tree.add(start, None), 0));
+        }
+    }
+    while let Some((id, moves)) = queue.pop_front() {
+        stats.iterate();
+        if stats.out_of_budget() {
+            stats.bound = Some(moves);
+            break;
+        }
+        let state = &tree.nodes[id].0;
+        if problem.is_goal(state) {
+            stats.seen = seen.len() as u64;
+            return (Some(tree.path(id, moves)), stats);
+        }
+        for (next, _) in problem.successors(state) {
+            if seen.insert(problem.key(&next)) {
+                queue.push_back((tree.add(next, Some(id)), moves + 1));
+            }
+        }
+    }
+    stats.seen = seen.len() as u64;
+    (None, stats)
+}
+
+/// Cheapest path.
+pub fn dijkstra<P, I>(problem: &P, starts: I) -> (Option<Path<P::State>>, Stats)
+where
+    P: Problem,
+    I: IntoIterator<Item = P::State>,
+{
+    cheapest(problem, starts, |_| 0)
+}
+
+/// Cheapest path, exploring first the states that `heuristic` finds closest
+/// to a goal. The heuristic must never overestimate the remaining cost.
+pub fn astar<P, I>(problem: &P, starts: I) -> (Option<Path<P::State>>, Stats)
+where
+    P: Problem,
+    I: IntoIterator<Item = P::State>,
+{
+    cheapest(problem, starts, |state| problem.heuristic(state))
+}
+
+fn cheapest<P, I, H>(problem: &P, starts: I, heuristic: H) -> (Option<Path<P::State>>, Stats)
+where
+    P: Problem,
+    I: IntoIterator<Item = P::State>,
+    H: Fn(&P::State) -> u64,
+{
+    let mut stats = Stats::default();
+    let mut tree = Tree::new();
+    // Lowest cost found to reach each key
+    let mut costs = HashMap::new();
+    // Lowest estimated total cost first
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        costs.insert(problem.key(&start), 0);
+        let estimate = heuristic(&start);
+        heap.push(Reverse((estimate, 0, tree.add(start, None))));
+    }
+    while let Some(Reverse((estimate, cost, id))) = heap.pop() {
+        let state = &tree.nodes[id].0;
+        if costs.get(&problem.key(state)).is_some_and(|&c| c < cost) {
+            // Reached again for cheaper since it was queued
+            continue;
+        }
+        stats.iterate();
+        if stats.out_of_budget() {
+            // The lowest estimate left
+            stats.bound = Some(estimate);
+            break;
+        }
+        if problem.is_goal(state) {
+            stats.seen = costs.len() as u64;
+            return (Some(tree.path(id, cost)), stats);
+        }
+        for (next, step) in problem.successors(state) {
+            let next_cost = cost + step;
+            match costs.entry(problem.key(&next)) {
+                Entry::Occupied(e) if *e.get() <= next_cost => continue,
+                Entry::Occupied(mut e) => {
+                    e.insert(next_cost);
+                }
+                Entry::Vacant(e) => {
+                    e.insert(next_cost);
+                }
+            }
+            let estimate = next_cost + heuristic(&next);
+            heap.push(Reverse((estimate, next_cost, tree.add(next, Some(id)))));
+        }
+    }
+    stats.seen = costs.len() as u64;
+    (None, stats)
+}
+
+/// Frontier entry of a best first exploration, by priority then latest first.
+struct Prioritized<S> {
+    priority: u64,
+    order: u64,
+    state: S,
+}
+
+impl<S> PartialEq for Prioritized<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S> Eq for Prioritized<S> {}
+
+impl<S> PartialOrd for Prioritized<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for Prioritized<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.priority, self.order).cmp(&(other.priority, other.order))
+    }
+}
+
+enum Frontier<S> {
+    Stack(Vec<S>),
+    Heap(BinaryHeap<Prioritized<S>>, u64),
+}
+
+// The heap as its entries and its order
+impl<S: Record> Record for Frontier<S> {
+    fn record(&self, numbers: &mut Vec<u64>) {
+        match self {
+            Frontier::Stack(stack) => {
+                numbers.push(0);
+                stack.record(numbers);
+            }
+            Frontier::Heap(heap, order) => {
+                numbers.extend([1, *order, heap.len() as u64]);
+                for entry in heap.iter() {
+                    (entry.priority, entry.order).record(numbers);
+                    entry.state.record(numbers);
+                }
+            }
+        }
+    }
+    fn restore(numbers: &mut impl Iterator<Item = u64>) -> Option<Frontier<S>> {
+        match numbers.next()? {
+            0 => Some(Frontier::Stack(Vec::restore(numbers)?)),
+            1 => {
+                let order = u64::restore(numbers)?;
+                let entries = Vec::<(u64, u64, S)>::restore(numbers)?;
+                let heap = entries
+                    .into_iter()
+                    .map(|(priority, order, state)| Prioritized {
+                        priority,
+                        order,
+                        state,
+                    })
+                    .collect();
+                Some(Frontier::Heap(heap, order))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<S> Frontier<S> {
+    fn push(&mut self, state: S, priority: u64) {
+        match self {
+            Frontier::Stack(stack) => stack.push(state),
+            Frontier::Heap(heap, order) => {
+                *order += 1;
+                heap.push(Prioritized {
+                    priority,
+                    order: *order,
+                    state,
+                });
+            }
+        }
+    }
+    fn pop(&mut self) -> Option<S> {
+        match self {
+            Frontier::Stack(stack) => stack.pop(),
+            Frontier::Heap(heap, _) => heap.pop().map(|p| p.state),
+        }
+    }
+}
+
+/// What `branch_and_bound` saves of its search to continue it.
+struct Bounding<P: Maximize> {
+    frontier: Frontier<P::State>,
+    // Non dominated marks of the explored states, by key
+    seen: LruCache<P::Key, Vec<P::Mark>>,
+    best: Best<P>,
+    stats: Stats,
+}
+
+impl<P: Maximize> Bounding<P>
+where
+    P::State: Record,
+    P::Score: Record,
+    P::Key: Record,
+    P::Mark: Record,
+{
+    fn new(start: P::State) -> Bounding<P> {
+        let mut frontier = if P::BEST_FIRST {
+            Frontier::Heap(BinaryHeap::new(), 0)
+        } else {
+            Frontier::Stack(vec![])
+        };
+        frontier.push(start, 0);
+        Bounding {
+            frontier,
+            seen: Bounding::<P>::seen(vec![]),
+            best: None,
+            stats: Stats::default(),
+        }
+    }
+    // The deduplication table from its least recently seen key
+    fn seen(entries: Vec<(P::Key, Vec<P::Mark>)>) -> LruCache<P::Key, Vec<P::Mark>> {
+        let capacity = NonZeroUsize::new(P::MAX_SEEN).unwrap_or(NonZeroUsize::MIN);
+        let mut seen = LruCache::new(capacity);
+        for (key, marks) in entries {
+            seen.put(key, marks);
+        }
+        seen
+    }
+    fn paused(&self) -> Saved {
+        let mut numbers = vec![];
+        self.frontier.record(&mut numbers);
+        numbers.push(self.seen.len() as u64);
+        for (key, marks) in self.seen.iter().rev() {
+            key.record(&mut numbers);
+            marks.record(&mut numbers);
+        }
+        self.best.record(&mut numbers);
+        self.stats.record(&mut numbers);
+        Saved::Paused(numbers)
+    }
+    fn resume(numbers: &[u64]) -> Option<Bounding<P>> {
+        let numbers = &mut numbers.iter().copied();
+        Some(Bounding {
+            frontier: Record::restore(numbers)?,
+            seen: Bounding::<P>::seen(Record::restore(numbers)?),
+            best: Record::restore(numbers)?,
+            stats: Record::restore(numbers)?,
+        })
+    }
+}
+
+/// Best score reachable from `start`, with the state reaching it.
+pub fn branch_and_bound<P: Maximize>(problem: &P, start: P::State) -> (Best<P>, Stats)
+where
+    P::State: Record,
+    P::Score: Record,
+    P::Key: Record,
+    P::Mark: Record,
+{
+    let checkpoint = checkpoint::begin();
+    let start_over = || {
+        warn!("Invalid checkpoint of the search, starting over");
+        Bounding::<P>::new(start.clone())
+    };
+    let mut search = match &checkpoint {
+        Some((_, Some(Saved::Done(numbers)))) => {
+            match Record::restore(&mut numbers.iter().copied()) {
+                Some(done) => return done,
+                None => start_over(),
+            }
+        }
+        Some((_, Some(Saved::Paused(numbers)))) => {
+            let search = Bounding::resume(numbers).unwrap_or_else(start_over);
+            debug!("Resumed after {} iterations", search.stats.iterations);
+            search
+        }
+        _ => Bounding::new(start),
+    };
+    let mut successors = vec![];
+    while let Some(state) = search.frontier.pop() {
+        search.stats.iterate();
+        let score = problem.score(&state);
+        if search.best.as_ref().is_none_or(|(b, _)| score > *b) {
+            debug!("Best improved after {} iterations", search.stats.iterations);
+            search.best = Some((score, state.clone()));
+        }
+        if search
+            .best
+            .as_ref()
+            .is_some_and(|(b, _)| problem.upper_bound(&state) <= *b)
+        {
+            search.stats.pruned += 1;
+        } else {
+            problem.successors(&state, &mut successors);
+            for next in successors.drain(..) {
+                if let Some((key, mark)) = problem.dedup(&next) {
+                    match search.seen.get_mut(&key) {
+                        Some(marks) => {
+                            if marks.iter().any(|m| problem.dominates(m, &mark)) {
+                                continue;
+                            }
+                            marks.retain(|m| !problem.dominates(&mark, m));
+                            marks.push(mark);
+                        }
+                        None => {
+                            search.seen.put(key, vec![mark]);
+                        }
+                    }
+                    search.stats.seen = search.seen.len() as u64;
+                }
+                let priority = if P::BEST_FIRST {
+                    problem.priority(&next)
+                } else {
+                    0
+                };
+                search.frontier.push(next, priority);
+            }
+        }
+        // Checked once the state is explored, the frontier is then complete
+        if search.stats.iterations.is_multiple_of(1024) {
+            let aborted = search.stats.out_of_budget();
+            if let (Some((number, _)), true) = (&checkpoint, aborted || checkpoint::due()) {
+                checkpoint::save(*number, search.paused());
+            }
+            if aborted {
+                break;
+            }
+        }
+    }
+    if let (Some((number, _)), None) = (checkpoint, search.stats.aborted) {
+        let mut numbers = vec![];
+        search.best.record(&mut numbers);
+        search.stats.record(&mut numbers);
+        checkpoint::save(number, Saved::Done(numbers));
+    }
+    (search.best, search.stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Moves on a line of numbers, either +1 for a cost of 1 or x2 for a cost of 3
+    struct Line {
+        goal: u64,
+    }
+
+    impl Problem for Line {
+        type State = u64;
+        type Key = u64;
+        fn successors(&self, &n: &u64) -> Vec<(u64, u64)> {
+            vec![(n + 1, 1), (n * 2, 3)]
+                .into_iter()
+                .filter(|&(m, _)| m <= self.goal)
+                .collect()
+        }
+        fn is_goal(&self, &n: &u64) -> bool {
+            n == self.goal
+        }
+        fn key(&self, &n: &u64) -> u64 {
+            n
+        }
+        fn heuristic(&self, &n: &u64) -> u64 {
+            // Any move costs at least 1
+            (n != self.goal) as u64
+        }
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let line = Line { goal: 20 };
+        let (path, _) = bfs(&line, [1]);
+        // 1 2 4 5 10 20
+        assert_eq!(path.unwrap().cost, 5);
+        let (path, _) = dijkstra(&line, [1]);
+        let path = path.unwrap();
+        // 1 2 3 4 5 10 20
+        assert_eq!(path.cost, 10);
+        assert_eq!(path.states, vec![1, 2, 3, 4, 5, 10, 20]);
+        let (path, _) = astar(&line, [1]);
+        assert_eq!(path.unwrap().cost, 10);
+        let (path, _) = bfs(&line, [21]);
+        assert_eq!(path, None);
+    }
+
+    // 0/1 knapsack: the state is (next item, weight, value)
+    struct Knapsack<const BEST_FIRST: bool> {
+        items: Vec<(u32, u32)>,
+        capacity: u32,
+    }
+
+    impl<const B: bool> Maximize for Knapsack<B> {
+        type State = (usize, u32, u32);
+        type Score = u32;
+        type Key = (usize, u32);
+        type Mark = u32;
+        const BEST_FIRST: bool = B;
+        fn successors(&self, &(i, weight, value): &Self::State, next: &mut Vec<Self::State>) {
+            let Some(&(w, v)) = self.items.get(i) else {
+                return;
+            };
+            next.push((i + 1, weight, value));
+            if weight + w <= self.capacity {
+                next.push((i + 1, weight + w, value + v));
+            }
+        }
+        fn score(&self, state: &Self::State) -> u32 {
+            state.2
+        }
+        fn upper_bound(&self, &(i, _, value): &Self::State) -> u32 {
+            value + self.items[i..].iter().map(|(_, v)| v).sum::<u32>()
+        }
+        fn priority(&self, state: &Self::State) -> u64 {
+            self.upper_bound(state) as u64
+        }
+        fn dedup(&self, &(i, weight, value): &Self::State) -> Option<((usize, u32), u32)> {
+            Some(((i, weight), value))
+        }
+        fn dominates(&self, seen: &u32, mark: &u32) -> bool {
+            seen >= mark
+        }
+    }
+
+    #[test]
+    fn best_score() {
+        let items = vec![(5, 10), (4, 40), (6, 30), (3, 50)];
+        let depth_first = Knapsack::<false> {
+            items: items.clone(),
+            capacity: 10,
+        };
+        let (best, stats) = branch_and_bound(&depth_first, (0, 0, 0));
+        assert_eq!(best.map(|(score, _)| score), Some(90));
+        assert!(stats.pruned > 0);
+        let best_first = Knapsack::<true> {
+            items,
+            capacity: 10,
+        };
+        let (best, _) = branch_and_bound(&best_first, (0, 0, 0));
+        assert_eq!(best, Some((90, (4, 7, 90))));
+    }
+
+    #[test]
+    fn budgets() {
+        budget::set(budget::Budget {
+            timeout: Some(std::time::Duration::ZERO),
+            max_memory: None,
+        });
+        budget::start();
+        let line = Line { goal: 5000 };
+        let (path, stats) = dijkstra(&line, [1]);
+        assert_eq!((path, stats.aborted), (None, Some(Abort::Timeout)));
+        assert_eq!(stats.iterations, 1024);
+        assert!(stats.bound.unwrap() > 0);
+
+        budget::start();
+        let items = (1..=60).map(|i| (i % 9 + 1, i * 7 % 11 + 1)).collect();
+        let knapsack = Knapsack::<false> {
+            items,
+            capacity: 100,
+        };
+        let (best, stats) = branch_and_bound(&knapsack, (0, 0, 0));
+        assert!(best.is_some() && stats.aborted.is_some());
+        assert_eq!(budget::take(), Some(Abort::Timeout));
+        budget::set(budget::Budget::default());
+    }
+
+    #[test]
+    fn checkpoints() {
+        let items = (1..=60).map(|i| (i % 9 + 1, i * 7 % 11 + 1)).collect();
+        let knapsack = Knapsack::<true> {
+            items,
+            capacity: 100,
+        };
+        let (best, stats) = branch_and_bound(&knapsack, (0, 0, 0));
+
+        let file = std::env::temp_dir().join(format!("search-checkpoint-{}", std::process::id()));
+        let every = std::time::Duration::from_secs(60);
+        checkpoint::set(&file, "knapsack", every, false).unwrap();
+        budget::set(budget::Budget {
+            timeout: Some(std::time::Duration::ZERO),
+            max_memory: None,
+        });
+        budget::start();
+        let (_, stopped) = branch_and_bound(&knapsack, (0, 0, 0));
+        assert_eq!(stopped.aborted, Some(Abort::Timeout));
+        budget::set(budget::Budget::default());
+        budget::start();
+
+        // Resumed, then done
+        for _ in 0..2 {
+            checkpoint::set(&file, "knapsack", every, true).unwrap();
+            let resumed = branch_and_bound(&knapsack, (0, 0, 0));
+            assert_eq!(resumed, (best, stats));
+        }
+        checkpoint::clear();
+        std::fs::remove_file(&file).unwrap();
+    }
+}